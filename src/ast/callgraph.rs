@@ -0,0 +1,76 @@
+//! Which functions call which, for documentation and for drawing with
+//! Graphviz.
+
+use super::statement::Root;
+
+/// Functions, in the order they're defined, and the calls between them. Each
+/// caller and callee pair appears once, however many times the call is made.
+#[derive(Debug, Default)]
+pub struct Graph {
+    pub nodes: Vec<String>,
+    pub edges: Vec<(String, String)>,
+}
+
+pub fn call_graph(root: &Root) -> Graph {
+    let mut graph = Graph::default();
+    for (caller, calls) in root.calls() {
+        graph.nodes.push(caller.to_string());
+        for callee in calls {
+            let edge = (caller.to_string(), callee);
+            if !graph.edges.contains(&edge) {
+                graph.edges.push(edge);
+            }
+        }
+    }
+    graph
+}
+
+impl Graph {
+    /// Renders the graph in Graphviz's DOT language, eg. for `dot -Tsvg`.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph calls {\n");
+        for node in &self.nodes {
+            dot.push_str(&format!("    \"{node}\";\n"));
+        }
+        for (caller, callee) in &self.edges {
+            dot.push_str(&format!("    \"{caller}\" -> \"{callee}\";\n"));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{parse, SymbolTable};
+    use crate::lex::{lex, LexConfig};
+
+    #[test]
+    fn main_calling_two_helpers() {
+        let src = "fn int one() { return 1; }
+fn int two() { return 2; }
+fn int main() { return one() + two() + one(); }";
+        let lexemes = lex(src.to_string(), &LexConfig::default()).unwrap();
+        let graph = call_graph(&parse(lexemes, &mut SymbolTable::default()).unwrap());
+        assert_eq!(graph.nodes, ["one", "two", "main"]);
+        assert_eq!(
+            graph.edges,
+            [
+                ("main".to_string(), "one".to_string()),
+                ("main".to_string(), "two".to_string())
+            ]
+        );
+        assert_eq!(
+            graph.to_dot(),
+            "digraph calls {
+    \"one\";
+    \"two\";
+    \"main\";
+    \"main\" -> \"one\";
+    \"main\" -> \"two\";
+}
+"
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use crate::ast::{
+    expression::{Expression, ExpressionTrait},
+    ir::{OutputWrapper, Temp},
+    ASTNode, PrimitiveType, SymbolTable,
+};
+use crate::{
+    consume,
+    lex::{Lexeme, Lexemes},
+};
+use anyhow::{bail, Context, Result};
+
+/// `<name>(<expr>, ...)`, calling a function defined earlier in the file.
+#[derive(Debug, Clone)]
+pub struct Call {
+    name: String,
+    args: Vec<Expression>,
+    return_type: PrimitiveType,
+    temp: Temp,
+}
+
+impl ASTNode for Call {
+    fn new(lexemes: &mut Lexemes, symtab: &mut SymbolTable) -> Result<Self> {
+        let name: String;
+        consume!(Lexeme::Idn(nm) in lexemes => name = nm)?;
+        consume!(Lexeme::OpenParen in lexemes)?;
+
+        let mut args = vec![];
+        while lexemes.front() != Some(&Lexeme::CloseParen) {
+            args.push(Expression::new(lexemes, symtab)?);
+            match lexemes.front_or_eof()? {
+                Lexeme::Delimiter => {
+                    consume!(Lexeme::Delimiter in lexemes)?;
+                    if lexemes.front() == Some(&Lexeme::CloseParen) {
+                        bail!("Expected another argument to `{name}` after the trailing `,`");
+                    }
+                }
+                Lexeme::CloseParen => {}
+                got => bail!("Expected `,` or `)` after an argument to `{name}`, got {got:?}"),
+            }
+        }
+        consume!(Lexeme::CloseParen in lexemes)?;
+
+        let signature = symtab
+            .functions
+            .get(&name)
+            .context(format!(
+                "Function `{name}` not found; functions must be defined before they're called"
+            ))?
+            .clone();
+        if args.len() != signature.params.len() {
+            bail!(
+                "`{name}` takes {} arguments, but was given {}",
+                signature.params.len(),
+                args.len()
+            );
+        }
+        for (idx, (arg, expected)) in args.iter().zip(&signature.params).enumerate() {
+            let found = arg.evaltype(symtab)?;
+            if found != *expected {
+                bail!(
+                    "Argument {} to `{name}` should be {expected}, but is {found}",
+                    idx + 1
+                );
+            }
+        }
+
+        Ok(Self {
+            name,
+            args,
+            return_type: signature.return_type,
+            temp: Temp::default(),
+        })
+    }
+
+    fn codegen(&self, ow: &mut OutputWrapper, symtab: &mut SymbolTable) {
+        for arg in &self.args {
+            arg.codegen(ow, symtab);
+        }
+        let args = self
+            .args
+            .iter()
+            .map(|arg| {
+                format!(
+                    "{} {}",
+                    arg.evaltype(symtab).unwrap().ir_type(),
+                    arg.eval(symtab).unwrap()
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let call = format!("call {} @{}({args})", self.return_type.ir_type(), self.name);
+        // void calls don't produce a value to name
+        if self.return_type == PrimitiveType::Void {
+            ow.appendln(call, 1);
+        } else {
+            let name = self.temp.assign(ow);
+            ow.appendln(format!("{name} = {call}"), 1);
+        }
+    }
+}
+
+impl ExpressionTrait for Call {
+    fn evaltype(&self, _symtab: &mut SymbolTable) -> Result<PrimitiveType> {
+        Ok(self.return_type)
+    }
+
+    fn eval(&self, _symtab: &mut SymbolTable) -> Result<String> {
+        if self.return_type == PrimitiveType::Void {
+            return Ok(String::new());
+        }
+        Ok(self.temp.get())
+    }
+
+    fn calls(&self) -> Vec<String> {
+        // the arguments are evaluated before the call itself
+        let mut calls: Vec<String> = self.args.iter().flat_map(|arg| arg.calls()).collect();
+        calls.push(self.name.clone());
+        calls
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::parse_src;
+
+    const ADD: &str = "fn int add(int a, int b) { return a + b; }\n";
+
+    fn call_in_main(call: &str) -> String {
+        parse_src(&format!("{ADD}fn int main() {{ return {call}; }}"))
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn trailing_comma_is_an_error() {
+        assert_eq!(
+            call_in_main("add(1, 2,)"),
+            "Expected another argument to `add` after the trailing `,`"
+        );
+    }
+
+    #[test]
+    fn arguments_need_separating() {
+        assert_eq!(
+            call_in_main("add(1 2)"),
+            "Expected `,` or `)` after an argument to `add`, got Literal(Integer(2))"
+        );
+    }
+
+    #[test]
+    fn calls_are_checked_against_the_signature() {
+        assert_eq!(
+            call_in_main("add(1)"),
+            "`add` takes 2 arguments, but was given 1"
+        );
+        assert_eq!(
+            call_in_main("add(1, true)"),
+            "Argument 2 to `add` should be int, but is bool"
+        );
+        assert_eq!(
+            call_in_main("sub(1, 2)"),
+            "Function `sub` not found; functions must be defined before they're called"
+        );
+    }
+}
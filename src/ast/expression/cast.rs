@@ -0,0 +1,101 @@
+use crate::ast::{
+    expression::{Expression, ExpressionTrait},
+    ir::{OutputWrapper, Temp},
+    ASTNode, PrimitiveType, SymbolTable,
+};
+use crate::{
+    consume,
+    lex::{Keyword, Lexeme, Lexemes},
+};
+use anyhow::{bail, Result};
+
+#[derive(Debug, Clone)]
+pub struct Cast {
+    expr: Expression,
+    target: PrimitiveType,
+    name: Temp,
+}
+
+impl Cast {
+    /// Parses the `as <type>` following `expr`.
+    pub fn from_expr(
+        expr: Expression,
+        lexemes: &mut Lexemes,
+        symtab: &mut SymbolTable,
+    ) -> Result<Self> {
+        consume!(Lexeme::Keyword(Keyword::Coercion) in lexemes)?;
+        let target = PrimitiveType::consume(lexemes)?;
+
+        let from = expr.evaltype(symtab)?;
+        // bools widen to any integer, and integers convert between widths;
+        // int -> bool should be spelt as a comparison so it's clear what
+        // counts as true
+        let to_int = target.int_bits().is_some()
+            && (from == PrimitiveType::Bool || from.int_bits().is_some());
+        if from != target && !to_int {
+            bail!("Can't cast a value of type {from} to {target}");
+        }
+
+        Ok(Self {
+            expr,
+            target,
+            name: Temp::default(),
+        })
+    }
+}
+
+impl ASTNode for Cast {
+    fn new(lexemes: &mut Lexemes, symtab: &mut SymbolTable) -> Result<Self> {
+        let expr = Expression::primary(lexemes, symtab)?;
+        Self::from_expr(expr, lexemes, symtab)
+    }
+
+    fn codegen(&self, ow: &mut OutputWrapper, symtab: &mut SymbolTable) {
+        self.expr.codegen(ow, symtab);
+        let from = self.expr.evaltype(symtab).unwrap();
+        // eg. `int` and `u64` only differ in how they're used
+        if from.ir_type() == self.target.ir_type() {
+            return;
+        }
+
+        let instruction = match (from.int_bits(), self.target.int_bits()) {
+            (Some(from_bits), Some(to_bits)) if to_bits < from_bits => "trunc",
+            (Some(_), _) if !from.is_unsigned() => "sext",
+            _ => "zext",
+        };
+        let name = self.name.assign(ow);
+        ow.appendln(
+            format!(
+                "{} = {} {} {} to {}",
+                name,
+                instruction,
+                from.ir_type(),
+                self.expr.eval(symtab).unwrap(),
+                self.target.ir_type()
+            ),
+            1,
+        );
+    }
+}
+
+impl ExpressionTrait for Cast {
+    fn evaltype(&self, _symtab: &mut SymbolTable) -> Result<PrimitiveType> {
+        Ok(self.target)
+    }
+
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<String> {
+        if self.expr.evaltype(symtab)?.ir_type() == self.target.ir_type() {
+            return self.expr.eval(symtab);
+        }
+        Ok(self.name.get())
+    }
+
+    fn calls(&self) -> Vec<String> {
+        self.expr.calls()
+    }
+
+    fn float_value(&self) -> Option<f64> {
+        // the only cast to a float is from a float
+        self.expr.float_value()
+    }
+}
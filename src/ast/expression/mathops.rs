@@ -1,206 +1,312 @@
 use crate::ast::{
     expression::{Expression, ExpressionTrait},
-    ir::OutputWrapper,
+    ir::{OutputWrapper, Temp},
     ASTNode, PrimitiveType, SymbolTable,
 };
-use crate::{consume, lex::Lexeme};
-use anyhow::{bail, Result};
-use std::collections::VecDeque;
+use crate::lex::{Lexeme, Lexemes};
+use anyhow::{bail, Context, Result};
 
-#[derive(Debug, Clone)]
-pub struct Addition(Expression, Expression);
-impl ASTNode for Addition {
-    fn new(lexemes: &mut VecDeque<Lexeme>, symtab: &mut SymbolTable) -> Result<Self> {
-        let idx = lexemes.iter().position(|l| l == &Lexeme::Addition).unwrap();
-        let rhs = Expression::new(&mut lexemes.drain(..idx).collect(), symtab)?;
-        consume!(Lexeme::Addition in lexemes)?;
-        let lhs = Expression::new(lexemes, symtab)?;
-        Ok(Self(rhs, lhs))
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    /// Truncated remainder (`srem`), so the result takes the sign of the
+    /// dividend, the same as Rust's `%`. Modulo by zero is the same
+    /// divide-by-zero error as `/`; see `Binary::from_lhs`.
+    Rem,
+    Eq,
+    Ne,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+}
 
-    fn codegen(&self, ow: &mut OutputWrapper, symtab: &mut SymbolTable) {
-        assert_eq!(
-            self.0.evaltype(symtab).unwrap(),
-            self.1.evaltype(symtab).unwrap()
-        );
-        ow.appendln(
-            format!(
-                "%{} = add {} {}, {}",
-                self.ir(symtab),
-                self.0.evaltype(symtab).unwrap().ir_type(),
-                self.0.eval(symtab).unwrap(),
-                self.1.eval(symtab).unwrap()
-            ),
-            1,
-        );
+/// The operator as it's written in source, eg. `/`.
+impl std::fmt::Display for BinOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+            Self::Rem => "%",
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Gt => ">",
+            Self::Le => "<=",
+            Self::Ge => ">=",
+        })
     }
 }
 
-impl ExpressionTrait for Addition {
-    fn evaltype(&self, symtab: &mut SymbolTable) -> Result<PrimitiveType> {
-        let lhs = self.0.evaltype(symtab)?;
-        assert_eq!(lhs, self.1.evaltype(symtab)?);
-        Ok(lhs)
+impl BinOp {
+    pub fn from_lexeme(lexeme: &Lexeme) -> Option<Self> {
+        Some(match lexeme {
+            Lexeme::Addition => Self::Add,
+            Lexeme::Subtraction => Self::Sub,
+            Lexeme::Multiplication => Self::Mul,
+            Lexeme::Division => Self::Div,
+            Lexeme::Modulo => Self::Rem,
+            Lexeme::DoubleEquals => Self::Eq,
+            Lexeme::BangEquals => Self::Ne,
+            Lexeme::AngleLeft => Self::Lt,
+            Lexeme::AngleRight => Self::Gt,
+            Lexeme::AngleLeftEquals => Self::Le,
+            Lexeme::AngleRightEquals => Self::Ge,
+            _ => return None,
+        })
     }
 
-    fn eval(&self, symtab: &mut SymbolTable) -> Result<String> {
-        // vv expression simplification
-        // self.0.eval() + self.1.eval()
-        // unreachable!()
-        Ok(format!("%{}", self.ir(symtab)))
+    /// How tightly the operator binds; see `expression::precedence`.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            Self::Eq | Self::Ne | Self::Lt | Self::Gt | Self::Le | Self::Ge => 1,
+            Self::Add | Self::Sub => 3,
+            Self::Mul | Self::Div | Self::Rem => 5,
+        }
     }
 
-    fn ir(&self, _symtab: &mut SymbolTable) -> String {
-        "addtemp".to_string()
+    pub fn is_comparison(&self) -> bool {
+        self.precedence() == 1
     }
-}
 
-#[derive(Debug, Clone)]
-pub struct Subtraction(Expression, Expression);
-impl ASTNode for Subtraction {
-    fn new(lexemes: &mut VecDeque<Lexeme>, symtab: &mut SymbolTable) -> Result<Self> {
-        let idx = lexemes
-            .iter()
-            .position(|l| l == &Lexeme::Subtraction)
-            .unwrap();
-        let rhs = Expression::new(&mut lexemes.drain(..idx).collect(), symtab)?;
-        consume!(Lexeme::Subtraction in lexemes)?;
-        let lhs = Expression::new(lexemes, symtab)?;
-        Ok(Self(rhs, lhs))
+    /// Applies the operator to two float constants, if it gives a float.
+    fn fold_float(&self, lhs: f64, rhs: f64) -> Option<f64> {
+        Some(match self {
+            Self::Add => lhs + rhs,
+            Self::Sub => lhs - rhs,
+            Self::Mul => lhs * rhs,
+            Self::Div => lhs / rhs,
+            // like `frem`, this takes the sign of the dividend
+            Self::Rem => lhs % rhs,
+            _ => return None,
+        })
     }
 
-    fn codegen(&self, ow: &mut OutputWrapper, symtab: &mut SymbolTable) {
-        assert_eq!(
-            self.0.evaltype(symtab).unwrap(),
-            self.1.evaltype(symtab).unwrap()
-        );
-        ow.appendln(
-            format!(
-                "%{} = sub {} {}, {}",
-                self.ir(symtab),
-                self.0.evaltype(symtab).unwrap().ir_type(),
-                self.0.eval(symtab).unwrap(),
-                self.1.eval(symtab).unwrap()
-            ),
-            1,
-        );
+    /// Applies the operator to two int constants, if it gives an int that
+    /// doesn't overflow.
+    fn fold_int(&self, lhs: i64, rhs: i64) -> Option<i64> {
+        match self {
+            Self::Add => lhs.checked_add(rhs),
+            Self::Sub => lhs.checked_sub(rhs),
+            Self::Mul => lhs.checked_mul(rhs),
+            Self::Div => lhs.checked_div(rhs),
+            Self::Rem => lhs.checked_rem(rhs),
+            _ => None,
+        }
     }
-}
 
-impl ExpressionTrait for Subtraction {
-    fn evaltype(&self, symtab: &mut SymbolTable) -> Result<PrimitiveType> {
-        let lhs = self.0.evaltype(symtab)?;
-        assert_eq!(lhs, self.1.evaltype(symtab)?);
-        Ok(lhs)
+    fn instruction(&self, ty: PrimitiveType) -> &str {
+        let float = ty == PrimitiveType::Float;
+        let unsigned = ty.is_unsigned();
+        // float comparisons are ordered, so anything against NaN is false,
+        // apart from `!=`, which is true whenever `==` is false
+        match self {
+            Self::Add if float => "fadd",
+            Self::Add => "add",
+            Self::Sub if float => "fsub",
+            Self::Sub => "sub",
+            Self::Mul if float => "fmul",
+            Self::Mul => "mul",
+            Self::Div if float => "fdiv",
+            Self::Div if unsigned => "udiv",
+            Self::Div => "sdiv",
+            Self::Rem if float => "frem",
+            Self::Rem if unsigned => "urem",
+            Self::Rem => "srem",
+            Self::Eq if float => "fcmp oeq",
+            Self::Eq => "icmp eq",
+            Self::Ne if float => "fcmp une",
+            Self::Ne => "icmp ne",
+            Self::Lt if float => "fcmp olt",
+            Self::Lt if unsigned => "icmp ult",
+            Self::Lt => "icmp slt",
+            Self::Gt if float => "fcmp ogt",
+            Self::Gt if unsigned => "icmp ugt",
+            Self::Gt => "icmp sgt",
+            Self::Le if float => "fcmp ole",
+            Self::Le if unsigned => "icmp ule",
+            Self::Le => "icmp sle",
+            Self::Ge if float => "fcmp oge",
+            Self::Ge if unsigned => "icmp uge",
+            Self::Ge => "icmp sge",
+        }
     }
+}
 
-    fn eval(&self, symtab: &mut SymbolTable) -> Result<String> {
-        // vv expression simplification
-        // self.0.eval() + self.1.eval()
-        // unreachable!()
-        Ok(format!("%{}", self.ir(symtab)))
-    }
+#[derive(Debug, Clone)]
+pub struct Binary {
+    op: BinOp,
+    lhs: Expression,
+    rhs: Expression,
+    name: Temp,
+}
+
+impl Binary {
+    /// Parses the operator and right operand following `lhs`. Operators that
+    /// bind tighter are folded into the right operand, and ones of the same
+    /// precedence are left for the caller, so `1 - 2 - 3` is `(1 - 2) - 3`.
+    pub fn from_lhs(
+        lhs: Expression,
+        lexemes: &mut Lexemes,
+        symtab: &mut SymbolTable,
+    ) -> Result<Self> {
+        let lexeme = lexemes.pop_front_or_eof()?;
+        let op = BinOp::from_lexeme(&lexeme)
+            .context(format!("Expected an arithmetic operator, got {lexeme:?}"))?;
+        let rhs = Expression::with_precedence(lexemes, symtab, op.precedence() + 1)?;
+        if op.is_comparison()
+            && lexemes
+                .front()
+                .and_then(BinOp::from_lexeme)
+                .is_some_and(|next| next.is_comparison())
+        {
+            bail!("Comparisons can't be chained, as in `a < b < c`");
+        }
 
-    fn ir(&self, _symtab: &mut SymbolTable) -> String {
-        "subtemp".to_string()
+        let (ltype, rtype) = (lhs.evaltype(symtab)?, rhs.evaltype(symtab)?);
+        if ltype != rtype {
+            bail!("Can't apply `{op}` to {ltype} and {rtype}");
+        }
+        if ltype == PrimitiveType::Void {
+            bail!("Can't apply `{op}` to void calls, as they don't produce a value");
+        }
+        // only a constant divisor can be checked; a variable that's zero at
+        // runtime is undefined behaviour, as with `sdiv` and `srem`
+        if matches!(op, BinOp::Div | BinOp::Rem) && rhs.int_value() == Some(0) {
+            bail!("Division by zero: the divisor of `{op}` is a constant 0");
+        }
+
+        let node = Self {
+            op,
+            lhs,
+            rhs,
+            name: Temp::default(),
+        };
+        // constants are always finite; NaN and infinities only come from
+        // arithmetic at runtime, which follows IEEE
+        if let Some(value) = node.float_value().filter(|value| !value.is_finite()) {
+            bail!("Constant float expression evaluates to {value}, but constants must be finite");
+        }
+        Ok(node)
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct Multiplication(Expression, Expression);
-impl ASTNode for Multiplication {
-    fn new(lexemes: &mut VecDeque<Lexeme>, symtab: &mut SymbolTable) -> Result<Self> {
-        let idx = lexemes
-            .iter()
-            .position(|l| l == &Lexeme::Multiplication)
-            .unwrap();
-        let rhs = Expression::new(&mut lexemes.drain(..idx).collect(), symtab)?;
-        consume!(Lexeme::Multiplication in lexemes)?;
-        let lhs = Expression::new(lexemes, symtab)?;
-        Ok(Self(rhs, lhs))
+impl ASTNode for Binary {
+    fn new(lexemes: &mut Lexemes, symtab: &mut SymbolTable) -> Result<Self> {
+        let lhs = Expression::primary(lexemes, symtab)?;
+        Self::from_lhs(lhs, lexemes, symtab)
     }
 
     fn codegen(&self, ow: &mut OutputWrapper, symtab: &mut SymbolTable) {
-        assert_eq!(
-            self.0.evaltype(symtab).unwrap(),
-            self.1.evaltype(symtab).unwrap()
-        );
+        self.lhs.codegen(ow, symtab);
+        self.rhs.codegen(ow, symtab);
+        let ty = self.lhs.evaltype(symtab).unwrap();
+        let name = self.name.assign(ow);
         ow.appendln(
             format!(
-                "%{} = mul {} {}, {}",
-                self.ir(symtab),
-                self.0.evaltype(symtab).unwrap().ir_type(),
-                self.0.eval(symtab).unwrap(),
-                self.1.eval(symtab).unwrap()
+                "{} = {} {} {}, {}",
+                name,
+                self.op.instruction(ty),
+                ty.ir_type(),
+                self.lhs.eval(symtab).unwrap(),
+                self.rhs.eval(symtab).unwrap()
             ),
             1,
         );
     }
 }
 
-impl ExpressionTrait for Multiplication {
+impl ExpressionTrait for Binary {
     fn evaltype(&self, symtab: &mut SymbolTable) -> Result<PrimitiveType> {
-        let lhs = self.0.evaltype(symtab)?;
-        assert_eq!(lhs, self.1.evaltype(symtab)?);
-        Ok(lhs)
+        if self.op.is_comparison() {
+            return Ok(PrimitiveType::Bool);
+        }
+        self.lhs.evaltype(symtab)
     }
 
-    fn eval(&self, symtab: &mut SymbolTable) -> Result<String> {
+    fn eval(&self, _symtab: &mut SymbolTable) -> Result<String> {
         // vv expression simplification
-        // self.0.eval() + self.1.eval()
+        // self.lhs.eval() + self.rhs.eval()
         // unreachable!()
-        Ok(format!("%{}", self.ir(symtab)))
+        Ok(self.name.get())
+    }
+
+    fn float_value(&self) -> Option<f64> {
+        self.op
+            .fold_float(self.lhs.float_value()?, self.rhs.float_value()?)
     }
 
-    fn ir(&self, _symtab: &mut SymbolTable) -> String {
-        "multemp".to_string()
+    fn int_value(&self) -> Option<i64> {
+        self.op
+            .fold_int(self.lhs.int_value()?, self.rhs.int_value()?)
+    }
+
+    fn calls(&self) -> Vec<String> {
+        let mut calls = self.lhs.calls();
+        calls.extend(self.rhs.calls());
+        calls
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct Division(Expression, Expression);
-impl ASTNode for Division {
-    fn new(lexemes: &mut VecDeque<Lexeme>, symtab: &mut SymbolTable) -> Result<Self> {
-        let idx = lexemes.iter().position(|l| l == &Lexeme::Division).unwrap();
-        let rhs = Expression::new(&mut lexemes.drain(..idx).collect(), symtab)?;
-        consume!(Lexeme::Division in lexemes)?;
-        let lhs = Expression::new(lexemes, symtab)?;
-        Ok(Self(rhs, lhs))
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lex::{lex, LexConfig};
+
+    fn parse(inp: &str) -> Result<Expression> {
+        let mut lexemes = lex(inp.to_string(), &LexConfig::default())?;
+        Expression::new(&mut lexemes, &mut SymbolTable::default())
     }
 
-    fn codegen(&self, ow: &mut OutputWrapper, symtab: &mut SymbolTable) {
+    #[test]
+    fn constant_nan_is_an_error() {
+        let err = parse("0.0 / 0.0").unwrap_err();
         assert_eq!(
-            self.0.evaltype(symtab).unwrap(),
-            self.1.evaltype(symtab).unwrap()
-        );
-        ow.appendln(
-            format!(
-                "%{} = div {} {}, {}",
-                self.ir(symtab),
-                self.0.evaltype(symtab).unwrap().ir_type(),
-                self.0.eval(symtab).unwrap(),
-                self.1.eval(symtab).unwrap()
-            ),
-            1,
+            err.to_string(),
+            "Constant float expression evaluates to NaN, but constants must be finite"
         );
     }
-}
 
-impl ExpressionTrait for Division {
-    fn evaltype(&self, symtab: &mut SymbolTable) -> Result<PrimitiveType> {
-        let lhs = self.0.evaltype(symtab)?;
-        assert_eq!(lhs, self.1.evaltype(symtab)?);
-        Ok(lhs)
+    #[test]
+    fn constant_infinity_is_an_error() {
+        let err = parse("(1.0 + 2.0) / (3.0 - 3.0)").unwrap_err();
+        assert!(err.to_string().contains("evaluates to inf"));
     }
 
-    fn eval(&self, symtab: &mut SymbolTable) -> Result<String> {
-        // vv expression simplification
-        // self.0.eval() + self.1.eval()
-        // unreachable!()
-        Ok(format!("%{}", self.ir(symtab)))
+    #[test]
+    fn modulo_by_zero_is_the_division_error() {
+        for inp in ["7 / 0", "7 % 0", "7 % (2 - 2)"] {
+            let err = parse(inp).unwrap_err().to_string();
+            assert!(err.starts_with("Division by zero"), "{inp}: {err}");
+        }
+    }
+
+    #[test]
+    fn remainder_takes_the_sign_of_the_dividend() {
+        assert_eq!(BinOp::Rem.fold_int(-7, 3), Some(-1));
+        assert_eq!(BinOp::Rem.fold_int(7, -3), Some(1));
+        assert_eq!(BinOp::Rem.fold_int(-7, -3), Some(-1));
+    }
+
+    #[test]
+    fn errors_name_operators_and_types_as_written() {
+        assert_eq!(
+            parse("1 + 2.0").unwrap_err().to_string(),
+            "Can't apply `+` to int and float"
+        );
+        assert_eq!(
+            parse("5 / 0").unwrap_err().to_string(),
+            "Division by zero: the divisor of `/` is a constant 0"
+        );
     }
 
-    fn ir(&self, _symtab: &mut SymbolTable) -> String {
-        "divtemp".to_string()
+    #[test]
+    fn finite_constants_are_fine() {
+        assert_eq!(parse("1.0 / 4.0").unwrap().float_value(), Some(0.25));
     }
 }
@@ -1,72 +1,92 @@
 use super::{ir::OutputWrapper, ASTNode, PrimitiveType, SymbolTable};
 use crate::{
     consume,
-    lex::{Lexeme, Literal},
+    lex::{Keyword, Lexeme, Lexemes, Literal},
 };
-use anyhow::{bail, Context, Result};
+use anyhow::{bail, Result};
 use dyn_clonable::clonable;
-use std::collections::VecDeque;
 
 mod mathops;
 pub use mathops::*;
 mod variable;
 pub use variable::*;
-mod comparisons;
-pub use comparisons::Comparison;
+mod cast;
+pub use cast::Cast;
+mod call;
+pub use call::Call;
 
 #[derive(Debug, Clone)]
 pub struct Expression {
     inner: Box<dyn ExpressionTrait>,
 }
 
-impl ASTNode for Expression {
-    fn new(lexemes: &mut VecDeque<Lexeme>, symtab: &mut SymbolTable) -> Result<Self> {
-        assert!(!lexemes.is_empty());
-        let expr = match lexemes.get(1) {
-            // TODO: hacky ASF!
-            Some(Lexeme::Newline) | Some(Lexeme::OpenBrace) | None => {
-                match lexemes
-                    .front()
-                    .context("Unexpected EOF whilst parsing expression")?
-                {
-                    Lexeme::Literal(_) => {
-                        Box::new(Literal::new(lexemes, symtab)?) as Box<dyn ExpressionTrait>
-                    }
-                    Lexeme::Idn(_) => {
-                        Box::new(Variable::new(lexemes, symtab)?) as Box<dyn ExpressionTrait>
-                    }
-                    unexpected => bail!("Could not construct an expression from {unexpected:?}"),
-                }
+/// Casts bind tighter than any binary operator; see `BinOp::precedence`.
+const CAST: u8 = 7;
+
+/// How tightly the operator at the front of `lexemes` binds, or `None` if the
+/// expression ends there.
+fn precedence(lexemes: &Lexemes) -> Option<u8> {
+    match lexemes.front()? {
+        Lexeme::Keyword(Keyword::Coercion) => Some(CAST),
+        op => BinOp::from_lexeme(op).map(|op| op.precedence()),
+    }
+}
+
+impl Expression {
+    /// A single operand: a literal, a variable, a call or a parenthesised
+    /// expression.
+    pub fn primary(lexemes: &mut Lexemes, symtab: &mut SymbolTable) -> Result<Self> {
+        if let Some(Lexeme::OpenParen) = lexemes.front() {
+            consume!(Lexeme::OpenParen in lexemes)?;
+            let inner = Self::new(lexemes, symtab)?;
+            // the lexer has already checked the parens balance, so this is
+            // something like `(1 2)`
+            match lexemes.pop_front_or_eof()? {
+                Lexeme::CloseParen => return Ok(inner),
+                got => bail!("Expected `)` to close the parenthesised expression, got {got:?}"),
             }
-            Some(next) => match next {
-                Lexeme::Addition => {
-                    Box::new(Addition::new(lexemes, symtab)?) as Box<dyn ExpressionTrait>
-                }
-                Lexeme::Subtraction => {
-                    Box::new(Subtraction::new(lexemes, symtab)?) as Box<dyn ExpressionTrait>
-                }
-                Lexeme::Multiplication => {
-                    Box::new(Multiplication::new(lexemes, symtab)?) as Box<dyn ExpressionTrait>
-                }
-                Lexeme::Division => {
-                    Box::new(Division::new(lexemes, symtab)?) as Box<dyn ExpressionTrait>
-                }
-                Lexeme::AngleLeft | Lexeme::AngleRight => {
-                    Box::new(Comparison::new(lexemes, symtab)?) as Box<dyn ExpressionTrait>
-                }
-                // TODO: fuck this
-                Lexeme::CloseParen => {
-                    Box::new(Expression::new(&mut lexemes.drain(..1).collect(), symtab)?)
-                        as Box<dyn ExpressionTrait>
-                }
-                _ => bail!(
-                    "Whilst parsing an expression, an unexpected token was encountered: {:?}",
-                    next
-                ),
-            },
+        }
+
+        let inner = match lexemes.front_or_eof()? {
+            Lexeme::Literal(_) => {
+                Box::new(Literal::new(lexemes, symtab)?) as Box<dyn ExpressionTrait>
+            }
+            Lexeme::Idn(_) if lexemes.get(1) == Some(&Lexeme::OpenParen) => {
+                Box::new(Call::new(lexemes, symtab)?) as Box<dyn ExpressionTrait>
+            }
+            Lexeme::Idn(_) => Box::new(Variable::new(lexemes, symtab)?) as Box<dyn ExpressionTrait>,
+            unexpected => bail!("Could not construct an expression from {unexpected:?}"),
         };
+        Ok(Self { inner })
+    }
 
-        Ok(Self { inner: expr })
+    /// Parses an expression, stopping at the first operator that binds less
+    /// tightly than `min` (or at anything that isn't an operator).
+    pub fn with_precedence(
+        lexemes: &mut Lexemes,
+        symtab: &mut SymbolTable,
+        min: u8,
+    ) -> Result<Self> {
+        let mut lhs = Self::primary(lexemes, symtab)?;
+        while let Some(prec) = precedence(lexemes) {
+            if prec < min {
+                break;
+            }
+            let inner = match prec {
+                CAST => {
+                    Box::new(Cast::from_expr(lhs, lexemes, symtab)?) as Box<dyn ExpressionTrait>
+                }
+                _ => Box::new(Binary::from_lhs(lhs, lexemes, symtab)?),
+            };
+            lhs = Self { inner };
+        }
+        Ok(lhs)
+    }
+}
+
+impl ASTNode for Expression {
+    fn new(lexemes: &mut Lexemes, symtab: &mut SymbolTable) -> Result<Self> {
+        Self::with_precedence(lexemes, symtab, 0)
     }
 
     fn codegen(&self, ow: &mut OutputWrapper, symtab: &mut SymbolTable) {
@@ -84,6 +104,15 @@ impl ExpressionTrait for Expression {
     fn ir(&self, symtab: &mut SymbolTable) -> String {
         self.inner.ir(symtab)
     }
+    fn calls(&self) -> Vec<String> {
+        self.inner.calls()
+    }
+    fn float_value(&self) -> Option<f64> {
+        self.inner.float_value()
+    }
+    fn int_value(&self) -> Option<i64> {
+        self.inner.int_value()
+    }
 }
 
 #[clonable]
@@ -93,12 +122,29 @@ pub trait ExpressionTrait: Clone + std::fmt::Debug + ASTNode {
     fn ir(&self, symtab: &mut SymbolTable) -> String {
         self.eval(symtab).unwrap()
     }
+    /// The functions the expression calls, in the order they're called.
+    fn calls(&self) -> Vec<String> {
+        vec![]
+    }
+    /// The value of a float expression made only of literals, worked out
+    /// at compile time.
+    fn float_value(&self) -> Option<f64> {
+        None
+    }
+    /// Likewise for an `int` expression, or `None` if working it out
+    /// overflows.
+    fn int_value(&self) -> Option<i64> {
+        None
+    }
 }
 
 impl ASTNode for Literal {
-    fn new(lexemes: &mut VecDeque<Lexeme>, _symtab: &mut SymbolTable) -> Result<Self> {
+    fn new(lexemes: &mut Lexemes, _symtab: &mut SymbolTable) -> Result<Self> {
         let node: Self;
         consume!(Lexeme::Literal(lit) in lexemes => node = lit)?;
+        if let Literal::Str(string) = &node {
+            bail!("String literals can't be used in expressions yet (given {string:?})");
+        }
         Ok(node)
     }
 
@@ -109,13 +155,38 @@ impl ExpressionTrait for Literal {
     fn evaltype(&self, _symtab: &mut SymbolTable) -> Result<PrimitiveType> {
         Ok(match self {
             Literal::Integer(_) => PrimitiveType::Int,
+            Literal::WideInteger(_) => PrimitiveType::I128,
+            Literal::Float(_) => PrimitiveType::Float,
+            Literal::Char(_) => PrimitiveType::Char,
+            Literal::Bool(_) => PrimitiveType::Bool,
+            Literal::Str(_) => unreachable!("string literals are rejected when parsed"),
         })
     }
 
     fn eval(&self, _symtab: &mut SymbolTable) -> Result<String> {
         Ok(match self {
-            Literal::Integer(int) => *int,
+            Literal::Integer(int) => int.to_string(),
+            Literal::WideInteger(int) => int.to_string(),
+            // LLVM only takes decimal doubles that are exactly representable,
+            // so always spell out the bits
+            Literal::Float(float) => format!("0x{:016X}", float.to_bits()),
+            Literal::Char(ch) => (*ch as u32).to_string(),
+            Literal::Bool(bool) => bool.to_string(),
+            Literal::Str(_) => unreachable!("string literals are rejected when parsed"),
+        })
+    }
+
+    fn float_value(&self) -> Option<f64> {
+        match self {
+            Literal::Float(float) => Some(*float),
+            _ => None,
+        }
+    }
+
+    fn int_value(&self) -> Option<i64> {
+        match self {
+            Literal::Integer(int) => Some(*int),
+            _ => None,
         }
-        .to_string())
     }
 }
@@ -1,48 +1,42 @@
 use crate::{
-    ast::{expression::ExpressionTrait, ir::OutputWrapper, ASTNode, PrimitiveType, SymbolTable},
+    ast::{
+        expression::ExpressionTrait,
+        ir::{OutputWrapper, Temp},
+        ASTNode, PrimitiveType, SymbolTable,
+    },
     consume,
-    lex::Lexeme,
+    lex::{Lexeme, Lexemes},
 };
-use anyhow::{bail, Context, Result};
-use std::collections::VecDeque;
+use anyhow::{Context, Result};
 
+/// A use of a variable, holding its name in the IR and its type. Both are
+/// looked up when parsing, so a later declaration that shadows it can't
+/// change what it refers to.
 #[derive(Debug, Clone, Default)]
-pub struct Variable(String);
+pub struct Variable(String, PrimitiveType, Temp);
 impl ASTNode for Variable {
-    fn new(lexemes: &mut VecDeque<Lexeme>, _symtab: &mut SymbolTable) -> Result<Self> {
-        let mut node = Self::default();
-        consume!(Lexeme::Idn(nm) in lexemes => node.0 = nm)?;
-        Ok(node)
+    fn new(lexemes: &mut Lexemes, symtab: &mut SymbolTable) -> Result<Self> {
+        let name: String;
+        consume!(Lexeme::Idn(nm) in lexemes => name = nm)?;
+        let var = symtab
+            .vars
+            .get(&name)
+            .context(format!("Variable {name} not found in scope"))?;
+        Ok(Self(var.ir_name.clone(), var.vtype, Temp::default()))
     }
 
-    fn codegen(&self, ow: &mut OutputWrapper, symtab: &mut SymbolTable) {
+    fn codegen(&self, ow: &mut OutputWrapper, _symtab: &mut SymbolTable) {
+        let name = self.2.assign(ow);
         ow.appendln(
-            format!(
-                "{} = load {}, ptr %{}",
-                self.ir(symtab),
-                self.evaltype(symtab).unwrap().ir_type(),
-                self.0
-            ),
+            format!("{} = load {}, ptr %{}", name, self.1.ir_type(), self.0),
             1,
         );
     }
 }
 
 impl ExpressionTrait for Variable {
-    fn evaltype(&self, symtab: &mut SymbolTable) -> Result<PrimitiveType> {
-        let var = symtab
-            .get(&self.0)
-            .context(format!("Variable {} not found in scope", self.0))?;
-
-        Ok(if let Some(strict) = var.vtype {
-            strict
-        } else {
-            let val = var.value.clone();
-            val.as_ref()
-                .unwrap()
-                .evaltype(symtab)
-                .context("The variable's value was another variable, not yet supported")?
-        })
+    fn evaltype(&self, _symtab: &mut SymbolTable) -> Result<PrimitiveType> {
+        Ok(self.1)
     }
 
     fn eval(&self, _symtab: &mut SymbolTable) -> Result<String> {
@@ -50,10 +44,6 @@ impl ExpressionTrait for Variable {
         // let var = symtab.get(nm).context(format!("Variable {nm} not found in scope"))?;
         // let val = var.value.clone().context(format!("The variable {nm} has no defined value"))?;
         // val.eval(symtab)?
-        unreachable!()
-    }
-
-    fn ir(&self, _symtab: &mut SymbolTable) -> String {
-        format!("%{}deref", self.0)
+        Ok(self.2.get())
     }
 }
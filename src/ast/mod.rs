@@ -1,62 +1,198 @@
-use crate::lex::Lexeme;
-use anyhow::{bail, Result};
-use std::collections::{HashMap, VecDeque};
+use crate::error::{ParseErrors, ShadowError};
+use crate::lex::{Keyword, Lexeme, Lexemes};
+use anyhow::{anyhow, bail, Error, Result};
+use std::collections::HashMap;
 
+pub mod callgraph;
 mod expression;
 mod statement;
 
 use expression::Expression;
 use ir::OutputWrapper;
-use statement::Root;
+pub use statement::Root;
 
+#[derive(Clone)]
 pub struct Var {
     pub name: String,
-    pub vtype: Option<PrimitiveType>,
+    /// What the variable's alloca is called in the IR.
+    pub ir_name: String,
+    pub vtype: PrimitiveType,
     pub value: Option<Expression>,
+    pub mutable: bool,
 }
 
-pub type SymbolTable = HashMap<String, Var>;
+impl Var {
+    /// Adds the variable to `symtab`, returning its `ir_name`. A name that's
+    /// already been declared in the function, even in a block that's since
+    /// ended, is given a fresh one, so every alloca can live in the same
+    /// function.
+    pub fn declare(mut self, symtab: &mut SymbolTable) -> String {
+        let declared = symtab.declared.entry(self.name.clone()).or_default();
+        if *declared > 0 {
+            self.ir_name = format!("{}.{}", self.name, declared);
+        }
+        *declared += 1;
+        let ir_name = self.ir_name.clone();
+        symtab.vars.insert(self.name.clone(), self);
+        ir_name
+    }
+}
+
+/// A function's parameter and return types, for checking calls against.
+#[derive(Debug, Clone)]
+pub struct Signature {
+    pub params: Vec<PrimitiveType>,
+    pub return_type: PrimitiveType,
+}
+
+#[derive(Default)]
+pub struct SymbolTable {
+    pub vars: HashMap<String, Var>,
+    pub functions: HashMap<String, Signature>,
+    /// How many times each name has been declared in the current function.
+    pub declared: HashMap<String, usize>,
+}
 
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
 pub enum PrimitiveType {
     // is this bad? this feels bad
     #[default]
     Void,
+    /// The natural word size, 64 bits; `i64` is the same type.
     Int,
+    I8,
+    I16,
+    I32,
+    U8,
+    U16,
+    U32,
+    U64,
+    /// Only for integer literals too large for an `int`, which need
+    /// `--wide-literals`.
+    I128,
     Bool,
+    Float,
+    Char,
 }
 
-impl PrimitiveType {
-    pub fn from_str(from: String) -> Result<Self> {
-        Ok(match from.as_str() {
+impl std::str::FromStr for PrimitiveType {
+    type Err = anyhow::Error;
+
+    fn from_str(from: &str) -> Result<Self> {
+        Ok(match from {
             "void" => Self::Void,
-            "int" => Self::Int,
+            "int" | "i64" => Self::Int,
+            "i8" => Self::I8,
+            "i16" => Self::I16,
+            "i32" => Self::I32,
+            "u8" => Self::U8,
+            "u16" => Self::U16,
+            "u32" => Self::U32,
+            "u64" => Self::U64,
+            "i128" => Self::I128,
             "bool" => Self::Bool,
+            "float" => Self::Float,
+            "char" => Self::Char,
             _ => bail!(
                 "'Custom' variable types not implemented yet (given {})",
                 from
             ),
         })
     }
+}
+
+/// The type's name as it's written in source, eg. `int`.
+impl std::fmt::Display for PrimitiveType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Void => "void",
+            Self::Int => "int",
+            Self::I8 => "i8",
+            Self::I16 => "i16",
+            Self::I32 => "i32",
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::I128 => "i128",
+            Self::Bool => "bool",
+            Self::Float => "float",
+            Self::Char => "char",
+        })
+    }
+}
+
+impl PrimitiveType {
+    /// Parses the type name at the front of `lexemes`. Errors point at
+    /// the lexeme that isn't a type.
+    pub fn consume(lexemes: &mut Lexemes) -> Result<Self> {
+        let pos = lexemes.pos();
+        let err = match lexemes.pop_front_or_eof()? {
+            Lexeme::Idn(name) => match name.parse() {
+                Ok(ptype) => return Ok(ptype),
+                Err(_) => ParseErrors::UnknownType(name),
+            },
+            got => ParseErrors::UnexpectedToken {
+                expected: "a type name".to_string(),
+                got: match got {
+                    Lexeme::Literal(lit) => lit.describe(),
+                    other => format!("{other:?}"),
+                },
+            },
+        };
+        Err(ShadowError::parse(err, pos).into())
+    }
+
+    /// The width of an integer type in bits, or `None` if it isn't one.
+    pub fn int_bits(&self) -> Option<u32> {
+        match self {
+            Self::I8 | Self::U8 => Some(8),
+            Self::I16 | Self::U16 => Some(16),
+            Self::I32 | Self::U32 => Some(32),
+            Self::Int | Self::U64 => Some(64),
+            Self::I128 => Some(128),
+            _ => None,
+        }
+    }
+
+    pub fn is_unsigned(&self) -> bool {
+        matches!(self, Self::U8 | Self::U16 | Self::U32 | Self::U64)
+    }
 
     fn ir_type(&self) -> &str {
         match self {
-            Self::Int => "i64", // TODO: Support other sizes of integer
+            // LLVM integers have no sign; it's picked by the instructions
+            Self::I8 | Self::U8 => "i8",
+            Self::I16 | Self::U16 => "i16",
+            Self::I32 | Self::U32 => "i32",
+            Self::Int | Self::U64 => "i64",
+            Self::I128 => "i128",
             Self::Void => "void",
             Self::Bool => "i1",
+            Self::Float => "double",
+            Self::Char => "i32", // a unicode scalar value, like Rust's `char`
         }
     }
 }
 
 #[macro_export]
 macro_rules! consume {
-    ( $variant:pat in $vec:expr => $then:stmt) => {
+    ( $variant:pat in $vec:expr => $then:stmt) => {{
+        use $crate::error::{ParseErrors, ShadowError};
+        let pos = $vec.pos();
         match $vec.pop_front() {
             Some($variant) => Ok::<(), anyhow::Error>({$then}),
-            None => bail!("Unexpected EOF"),
-            got => bail!("Expected {}, got {:?}. Remaining tokens were:\n{:?}", stringify!($variant), got, $vec),
-         }
-    };
+            None => return Err(ShadowError::parse(ParseErrors::UnexpectedEof, pos).into()),
+            Some(got) => return Err(ShadowError::parse(
+                ParseErrors::UnexpectedToken {
+                    expected: stringify!($variant).to_string(),
+                    got: format!("{got:?}"),
+                },
+                pos,
+            )
+            .into()),
+        }
+    }};
     ( $($variant:pat),+ in $vec:expr) => {
         $(
         consume!($variant in $vec => {})
@@ -65,49 +201,173 @@ macro_rules! consume {
 }
 
 pub trait ASTNode: std::fmt::Debug {
-    fn new(tokens: &mut VecDeque<Lexeme>, symtab: &mut SymbolTable) -> Result<Self>
+    fn new(tokens: &mut Lexemes, symtab: &mut SymbolTable) -> Result<Self>
     where
         Self: Sized;
 
     fn codegen(&self, ow: &mut OutputWrapper, symtab: &mut SymbolTable);
 }
 
-pub fn parse(lexemes: Vec<Lexeme>, symtab: &mut SymbolTable) -> Result<Root> {
-    Root::new(&mut VecDeque::from(lexemes), symtab)
+pub fn parse(mut lexemes: Lexemes, symtab: &mut SymbolTable) -> Result<Root> {
+    parse_deque(&mut lexemes, symtab)
+}
+
+/// Parses from the front of `lexemes`, leaving anything after the root
+/// (eg. a stray `}`) in place for the caller to inspect.
+pub fn parse_deque(lexemes: &mut Lexemes, symtab: &mut SymbolTable) -> Result<Root> {
+    Root::new(lexemes, symtab)
+}
+
+/// Lexes and parses `src` with the default config, for tests.
+#[cfg(test)]
+pub(crate) fn parse_src(src: &str) -> Result<Root> {
+    parse(
+        crate::lex::lex(src.to_string(), &Default::default())?,
+        &mut SymbolTable::default(),
+    )
+}
+
+/// Parses each top-level item on its own, so an error in one function
+/// doesn't hide the errors in the rest. Every error is returned, in the
+/// order they appear in the file.
+pub fn parse_all(
+    lexemes: Lexemes,
+    symtab: &mut SymbolTable,
+) -> std::result::Result<Root, Vec<Error>> {
+    let mut root = Root::default();
+    let mut errors = vec![];
+    let starts = top_level_items(&lexemes);
+    for mut item in lexemes.split_before(&starts) {
+        let (vars, declared) = (symtab.vars.clone(), symtab.declared.clone());
+        match parse_deque(&mut item, symtab) {
+            Ok(parsed) if item.is_empty() => root.stmts.extend(parsed.stmts),
+            Ok(_) => errors.push(anyhow!(
+                "Unexpected `}}` at {} with no block to close",
+                item.pos()
+            )),
+            Err(err) => {
+                // a function that fails part way doesn't get to restore the
+                // scope it was parsing in
+                symtab.vars = vars;
+                symtab.declared = declared;
+                errors.push(err);
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(root)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Where each `fn` that isn't inside a block starts, other than one at the
+/// very start.
+fn top_level_items(lexemes: &Lexemes) -> Vec<usize> {
+    let mut starts = vec![];
+    let mut depth = 0usize;
+    for (idx, lexeme) in lexemes.iter().enumerate() {
+        match lexeme {
+            Lexeme::OpenBrace => depth += 1,
+            Lexeme::CloseBrace => depth = depth.saturating_sub(1),
+            Lexeme::Keyword(Keyword::Fn) if depth == 0 && idx > 0 => starts.push(idx),
+            _ => {}
+        }
+    }
+    starts
 }
 
 pub mod ir {
     use super::{ASTNode, Root, SymbolTable};
     use std::{
+        cell::RefCell,
         fs::File,
         io::{BufWriter, Write},
     };
 
+    /// Hands out temporary and label names in the order they're requested,
+    /// so the same program always lowers to the same IR.
+    #[derive(Default)]
+    pub struct NameGen {
+        temps: usize,
+        labels: usize,
+    }
+
+    impl NameGen {
+        pub fn temp(&mut self) -> String {
+            self.temps += 1;
+            format!("%t{}", self.temps - 1)
+        }
+
+        pub fn label(&mut self) -> String {
+            self.labels += 1;
+            format!(".L{}", self.labels - 1)
+        }
+    }
+
+    /// The name an expression's result was given during codegen.
+    #[derive(Debug, Clone, Default)]
+    pub struct Temp(RefCell<Option<String>>);
+
+    impl Temp {
+        pub fn assign(&self, ow: &mut OutputWrapper) -> String {
+            let name = ow.names.temp();
+            *self.0.borrow_mut() = Some(name.clone());
+            name
+        }
+
+        pub fn get(&self) -> String {
+            self.0
+                .borrow()
+                .clone()
+                .expect("temporary used before its expression was generated")
+        }
+    }
+
+    /// Collects the IR as it's generated, writing it to a file on `flush`
+    /// if one was given.
     pub struct OutputWrapper {
-        file: BufWriter<File>,
+        file: Option<BufWriter<File>>,
+        ir: String,
+        pub names: NameGen,
     }
 
     impl OutputWrapper {
         pub fn new(path: String) -> std::io::Result<Self> {
             Ok(Self {
-                file: BufWriter::new(File::create(path)?),
+                file: Some(BufWriter::new(File::create(path)?)),
+                ..Self::in_memory()
             })
         }
 
+        /// An `OutputWrapper` with no file, whose IR is read with `into_ir`.
+        pub fn in_memory() -> Self {
+            Self {
+                file: None,
+                ir: String::new(),
+                names: NameGen::default(),
+            }
+        }
+
         pub fn append(&mut self, extra: String, idnt: usize) {
-            self.file
-                .write_all(vec![b' '; idnt * 4].as_slice())
-                .unwrap();
-            self.file.write(extra.as_bytes()).map(|_| ()).unwrap();
+            self.ir.push_str(&" ".repeat(idnt * 4));
+            self.ir.push_str(&extra);
         }
 
         pub fn appendln(&mut self, extra: String, idnt: usize) {
             self.append(extra, idnt);
-            self.file.write_all(&[b'\n']).unwrap();
+            self.ir.push('\n');
         }
 
         pub fn flush(&mut self) {
-            self.file.flush().map(|_| ()).unwrap();
+            if let Some(file) = &mut self.file {
+                file.write_all(self.ir.as_bytes()).unwrap();
+                file.flush().unwrap();
+            }
+        }
+
+        pub fn into_ir(self) -> String {
+            self.ir
         }
     }
 
@@ -115,3 +375,116 @@ pub mod ir {
         ast.codegen(ow, symtab)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrType;
+    use crate::lex::{lex, LexConfig, PosInfo};
+
+    fn lex_str(inp: &str) -> Lexemes {
+        lex(inp.to_string(), &LexConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn sized_integer_type_names() {
+        for (name, ty) in [
+            ("i8", PrimitiveType::I8),
+            ("i16", PrimitiveType::I16),
+            ("i32", PrimitiveType::I32),
+            ("i64", PrimitiveType::Int),
+            ("int", PrimitiveType::Int),
+            ("u8", PrimitiveType::U8),
+            ("u16", PrimitiveType::U16),
+            ("u32", PrimitiveType::U32),
+            ("u64", PrimitiveType::U64),
+        ] {
+            assert_eq!(name.parse::<PrimitiveType>().unwrap(), ty, "{name}");
+        }
+        assert_eq!(
+            "Foo".parse::<PrimitiveType>().unwrap_err().to_string(),
+            "'Custom' variable types not implemented yet (given Foo)"
+        );
+    }
+
+    #[test]
+    fn type_names_parse_with_from_str() {
+        assert_eq!("int".parse::<PrimitiveType>().unwrap(), PrimitiveType::Int);
+        assert_eq!(
+            "void".parse::<PrimitiveType>().unwrap(),
+            PrimitiveType::Void
+        );
+    }
+
+    #[test]
+    fn parse_deque_leaves_what_follows_the_root() {
+        let mut lexemes = lex_str("fn int a() { return 1; }");
+        let after = PosInfo {
+            line: 1,
+            column: 26,
+        };
+        lexemes.push_back(Lexeme::CloseBrace, after);
+        lexemes.push_back(Lexeme::Idn("rest".to_string()), after);
+        let root = parse_deque(&mut lexemes, &mut SymbolTable::default()).unwrap();
+        assert_eq!(root.stmts.len(), 1);
+        assert_eq!(lexemes.pos(), after);
+        assert_eq!(
+            Vec::from(lexemes),
+            [Lexeme::CloseBrace, Lexeme::Idn("rest".to_string())]
+        );
+    }
+
+    #[test]
+    fn syntax_errors_carry_their_position() {
+        let err = parse(
+            lex_str("fn int 5() { return 1; }"),
+            &mut SymbolTable::default(),
+        )
+        .unwrap_err();
+        let err = err.downcast_ref::<ShadowError>().unwrap();
+        assert!(matches!(
+            err.kind,
+            ErrType::Parse(ParseErrors::UnexpectedToken { .. })
+        ));
+        assert_eq!(err.pos, PosInfo { line: 1, column: 8 });
+    }
+
+    #[test]
+    fn return_at_the_end_of_the_file_points_at_the_end() {
+        let err = parse_src("fn int main() {\n    return 0;\n}\nreturn").unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ShadowError>(),
+            Some(&ShadowError::parse(
+                ParseErrors::UnexpectedEof,
+                PosInfo { line: 4, column: 7 }
+            ))
+        );
+    }
+
+    #[test]
+    fn unknown_types_point_at_their_name() {
+        for (src, column) in [("fn Foo f() {}", 4), ("fn int f(Foo x) {}", 10)] {
+            let err = parse(lex_str(src), &mut SymbolTable::default()).unwrap_err();
+            assert_eq!(
+                err.downcast_ref::<ShadowError>(),
+                Some(&ShadowError::parse(
+                    ParseErrors::UnknownType("Foo".to_string()),
+                    PosInfo { line: 1, column }
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn literal_return_type_is_named() {
+        let err = parse(
+            lex_str("fn 5 f() { return 1; }"),
+            &mut SymbolTable::default(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[SDW E/P] line 1, column 4: Expected a type name, got integer literal `5`"
+        );
+    }
+}
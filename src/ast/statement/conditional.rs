@@ -1,8 +1,7 @@
-use crate::ast::{expression::{Expression, ExpressionTrait}, statement::Block, ASTNode, OutputWrapper, SymbolTable};
+use crate::ast::{expression::{Expression, ExpressionTrait}, statement::Block, ASTNode, OutputWrapper, PrimitiveType, SymbolTable};
 use crate::consume;
-use crate::lex::{Keyword, Lexeme};
-use anyhow::{bail, Context, Result};
-use std::collections::VecDeque;
+use crate::lex::{Keyword, Lexeme, Lexemes};
+use anyhow::{bail, Result};
 
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -12,22 +11,61 @@ pub struct Conditional {
     else_block: Option<Block>,
 }
 
+impl Conditional {
+    /// Every block the conditional might run, including the `else`.
+    pub fn blocks(&self) -> Vec<&Block> {
+        let mut blocks = vec![&self.cond.body];
+        if let Some(elifs) = &self.elifs {
+            blocks.extend(elifs.iter().map(|cnditm| &cnditm.body));
+        }
+        blocks.extend(&self.else_block);
+        blocks
+    }
+
+    /// Without an `else` there's always a path that skips every block.
+    pub fn always_returns(&self) -> bool {
+        self.else_block.is_some() && self.blocks().iter().all(|block| block.always_returns())
+    }
+
+    /// The functions called by the conditions and blocks, in order.
+    pub fn calls(&self) -> Vec<String> {
+        let mut calls = vec![];
+        for cnditm in self.items() {
+            calls.extend(cnditm.expr.calls());
+            calls.extend(cnditm.body.calls());
+        }
+        calls.extend(self.else_block.iter().flat_map(|e| e.calls()));
+        calls
+    }
+
+    /// The `if` and every `else if` after it, in order.
+    fn items(&self) -> Vec<&ConditionalItem> {
+        let mut items = vec![&self.cond];
+        if let Some(elifs) = &self.elifs {
+            items.extend(elifs);
+        }
+        items
+    }
+}
+
 impl ASTNode for Conditional {
-    fn new(lexemes: &mut VecDeque<Lexeme>, symtab: &mut SymbolTable) -> Result<Self> {
-        let cond =
-            ConditionalItem::new(lexemes, symtab)?.context("malformed if expression, maybe")?;
+    fn new(lexemes: &mut Lexemes, symtab: &mut SymbolTable) -> Result<Self> {
+        consume!(Lexeme::Keyword(Keyword::If) in lexemes)?;
+        let cond = ConditionalItem::new(lexemes, symtab)?;
 
+        // only an `else` directly followed by `if` continues the chain; a
+        // bare `if` on the next line starts a new conditional
         let mut elifs = Vec::new();
-        while let Some(conditem) = ConditionalItem::new(lexemes, symtab)? {
-            elifs.push(conditem);
-            // this is probably maybe possibly bad
-            if let Some(Lexeme::CloseBrace) = lexemes.front() {
-                break;
-            }
+        while lexemes.front() == Some(&Lexeme::Keyword(Keyword::Else))
+            && lexemes.get(1) == Some(&Lexeme::Keyword(Keyword::If))
+        {
+            consume!(Lexeme::Keyword(Keyword::Else) in lexemes)?;
+            consume!(Lexeme::Keyword(Keyword::If) in lexemes)?;
+            elifs.push(ConditionalItem::new(lexemes, symtab)?);
         }
 
         let else_block = if let Lexeme::Keyword(Keyword::Else) =
-            lexemes.front().context("unexpected eof")?
+            lexemes.front_or_eof()?
         {
             lexemes.pop_front();
             Some(Block::new(lexemes, symtab)?)
@@ -43,14 +81,26 @@ impl ASTNode for Conditional {
     }
 
     fn codegen(&self, ow: &mut OutputWrapper, symtab: &mut SymbolTable) {
-        self.cond.codegen(ow, symtab);
-        if let Some(elifs) = &self.elifs {
-            for cnditm in elifs {
-                cnditm.codegen(ow, symtab);
-            }
+        // each condition jumps to its body, or on to the next condition; the
+        // last one falls through to the `else`, or past the whole chain
+        let items = self.items();
+        let labels: Vec<(String, String)> = items.iter().map(|_| (ow.names.label(), ow.names.label())).collect();
+        let end = match self.else_block {
+            Some(_) => ow.names.label(),
+            None => labels.last().unwrap().1.clone(),
+        };
+
+        for (cnditm, (iftrue, iffalse)) in items.into_iter().zip(&labels) {
+            cnditm.codegen(ow, symtab, iftrue, iffalse, &end);
         }
         if let Some(e) = &self.else_block {
             e.codegen(ow, symtab);
+            if !e.always_returns() {
+                ow.appendln(format!("br label %{end}"), 1);
+            }
+            if !self.always_returns() {
+                ow.appendln(format!("{end}:"), 0);
+            }
         }
     }
 }
@@ -63,32 +113,31 @@ pub struct ConditionalItem {
 }
 
 impl ConditionalItem {
-    fn new(lexemes: &mut VecDeque<Lexeme>, symtab: &mut SymbolTable) -> Result<Option<Self>> {
-        match lexemes.front() {
-            Some(&Lexeme::Keyword(Keyword::If)) => {
-                consume!(Lexeme::Keyword(Keyword::If) in lexemes)?
-            }
-            Some(&Lexeme::Keyword(Keyword::Else)) => {
-                if lexemes.get(1) != Some(&Lexeme::Keyword(Keyword::If)) {
-                    return Ok(None);
-                }
-                consume!(Lexeme::Keyword(Keyword::Else) in lexemes)?;
-                consume!(Lexeme::Keyword(Keyword::If) in lexemes)?;
-            }
-            _ => return Ok(None),
+    /// Parses the condition and body following an `if`.
+    fn new(lexemes: &mut Lexemes, symtab: &mut SymbolTable) -> Result<Self> {
+        if let Some(Lexeme::OpenBrace) = lexemes.front() {
+            bail!("Expected a condition after `if`, before its `{{`");
+        }
+        let expr = Expression::new(lexemes, symtab)?;
+        let ctype = expr.evaltype(symtab)?;
+        if ctype != PrimitiveType::Bool {
+            bail!("The condition of an `if` must be a bool, not {ctype}");
         }
 
-        Ok(Some(Self {
-            expr: Expression::new(lexemes, symtab)?,
+        Ok(Self {
+            expr,
             body: Block::new(lexemes, symtab)?,
-        }))
+        })
     }
 
-    fn codegen(&self, ow: &mut OutputWrapper, symtab: &mut SymbolTable) {
+    fn codegen(&self, ow: &mut OutputWrapper, symtab: &mut SymbolTable, iftrue: &str, iffalse: &str, end: &str) {
         self.expr.codegen(ow, symtab);
-        ow.appendln(format!("br i1 %{}, label %IfTrue, label %IfFalse", self.expr.ir(symtab)), 1);
-        ow.appendln("IfTrue:".to_string(), 0);
+        ow.appendln(format!("br i1 {}, label %{iftrue}, label %{iffalse}", self.expr.ir(symtab)), 1);
+        ow.appendln(format!("{iftrue}:"), 0);
         self.body.codegen(ow, symtab);
-        ow.appendln("IfFalse:".to_string(), 0);
+        if !self.body.always_returns() {
+            ow.appendln(format!("br label %{end}"), 1);
+        }
+        ow.appendln(format!("{iffalse}:"), 0);
     }
 }
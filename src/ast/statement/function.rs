@@ -1,8 +1,9 @@
-use crate::ast::{ir::OutputWrapper, statement::Block, ASTNode, PrimitiveType, SymbolTable};
+use crate::ast::{
+    ir::OutputWrapper, statement::Block, ASTNode, PrimitiveType, Signature, SymbolTable, Var,
+};
 use crate::consume;
-use crate::lex::{Keyword, Lexeme};
+use crate::lex::{Keyword, Lexeme, Lexemes};
 use anyhow::{bail, Result};
-use std::collections::VecDeque;
 
 #[derive(Debug, Default)]
 pub struct Parameter {
@@ -11,12 +12,11 @@ pub struct Parameter {
 }
 
 impl ASTNode for Parameter {
-    fn new(lexemes: &mut VecDeque<Lexeme>, _symtab: &mut SymbolTable) -> Result<Self> {
-        let mut node = Self::default();
-
-        consume!(Lexeme::Idn(pmt) in lexemes => {
-            node.pm_type = PrimitiveType::from_str(pmt)?;
-        })?;
+    fn new(lexemes: &mut Lexemes, _symtab: &mut SymbolTable) -> Result<Self> {
+        let mut node = Self {
+            pm_type: PrimitiveType::consume(lexemes)?,
+            ..Default::default()
+        };
         consume!(Lexeme::Idn(nm) in lexemes => {
             node.name = nm;
         })?;
@@ -24,8 +24,15 @@ impl ASTNode for Parameter {
         Ok(node)
     }
 
-    fn codegen(&self, _ow: &mut OutputWrapper, _symtab: &mut SymbolTable) {
-        todo!()
+    /// Copies the parameter into an alloca, so it's used like any other
+    /// variable.
+    fn codegen(&self, ow: &mut OutputWrapper, _symtab: &mut SymbolTable) {
+        let ty = self.pm_type.ir_type();
+        ow.appendln(format!("%{}.addr = alloca {ty}", self.name), 1);
+        ow.appendln(
+            format!("store {ty} %{}, ptr %{}.addr", self.name, self.name),
+            1,
+        );
     }
 }
 
@@ -38,13 +45,16 @@ pub struct Function {
 }
 
 impl ASTNode for Function {
-    fn new(lexemes: &mut VecDeque<Lexeme>, symtab: &mut SymbolTable) -> Result<Self> {
+    fn new(lexemes: &mut Lexemes, symtab: &mut SymbolTable) -> Result<Self> {
         let mut node = Function::default();
 
         consume!(Lexeme::Keyword(Keyword::Fn) in lexemes)?;
-        consume!(Lexeme::Idn(tp) in lexemes => {
-            node.return_type = PrimitiveType::from_str(tp)?;
-        })?;
+        // the return type either comes first, as in `fn int main()`, or
+        // after an arrow, as in `fn main() -> int`
+        let leading_type = !matches!(lexemes.get(1), Some(Lexeme::OpenParen));
+        if leading_type {
+            node.return_type = PrimitiveType::consume(lexemes)?;
+        }
         consume!(Lexeme::Idn(nm) in lexemes => {
             node.name = nm;
         })?;
@@ -63,7 +73,63 @@ impl ASTNode for Function {
         }
 
         consume!(Lexeme::CloseParen in lexemes)?;
+        if let Some(Lexeme::Arrow) = lexemes.front() {
+            if leading_type {
+                bail!("Function `{}` gives its return type twice", node.name);
+            }
+            consume!(Lexeme::Arrow in lexemes)?;
+            node.return_type = PrimitiveType::consume(lexemes)?;
+        }
+        // there's no way to pass arguments to a program, and its exit code
+        // is the only result
+        if node.name == "main" {
+            if let Some(pm) = node.params.first() {
+                bail!("`main` must take no parameters, but takes `{}`", pm.name);
+            }
+            if !matches!(node.return_type, PrimitiveType::Int | PrimitiveType::Void) {
+                bail!("`main` must return int or void, not {}", node.return_type);
+            }
+        }
+
+        let signature = Signature {
+            params: node.params.iter().map(|pm| pm.pm_type).collect(),
+            return_type: node.return_type,
+        };
+        // registered before the body is parsed, so functions can recurse
+        if symtab
+            .functions
+            .insert(node.name.clone(), signature)
+            .is_some()
+        {
+            bail!("Function `{}` is defined more than once", node.name);
+        }
+
+        // a function only sees its own parameters and variables; parameters
+        // are copied into allocas so they're used like any other variable
+        let outer = std::mem::take(&mut symtab.vars);
+        let outer_declared = std::mem::take(&mut symtab.declared);
+        for pm in &node.params {
+            Var {
+                name: pm.name.clone(),
+                ir_name: format!("{}.addr", pm.name),
+                vtype: pm.pm_type,
+                value: None,
+                mutable: false,
+            }
+            .declare(symtab);
+        }
+
         node.body = Block::new(lexemes, symtab)?;
+        node.body.check_returns(node.return_type, symtab)?;
+        if node.return_type != PrimitiveType::Void && !node.body.always_returns() {
+            bail!(
+                "Function `{}` returns {}, but not on every path",
+                node.name,
+                node.return_type
+            );
+        }
+        symtab.vars = outer;
+        symtab.declared = outer_declared;
         Ok(node)
     }
 
@@ -81,7 +147,41 @@ impl ASTNode for Function {
             ),
             0,
         );
+        for pm in &self.params {
+            pm.codegen(ow, symtab);
+        }
         self.body.codegen(ow, symtab);
+        // every block needs a terminator, even when a void function just
+        // runs off its end
+        if self.return_type == PrimitiveType::Void && !self.body.always_returns() {
+            ow.appendln("ret void".to_string(), 1);
+        }
         ow.appendln("}".to_string(), 0);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::parse_src;
+
+    #[test]
+    fn main_can_return_int_or_void() {
+        assert!(parse_src("fn int main() { return 0; }").is_ok());
+        assert!(parse_src("fn void main() { }").is_ok());
+    }
+
+    #[test]
+    fn main_cant_take_parameters() {
+        let err = parse_src("fn int main(int x) { return x; }").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`main` must take no parameters, but takes `x`"
+        );
+    }
+
+    #[test]
+    fn main_cant_return_other_types() {
+        let err = parse_src("fn bool main() { return true; }").unwrap_err();
+        assert_eq!(err.to_string(), "`main` must return int or void, not bool");
+    }
+}
@@ -5,34 +5,39 @@ use super::{
 };
 use crate::{
     consume,
-    lex::{Keyword, Lexeme},
+    lex::{Keyword, Lexeme, Lexemes},
 };
-use anyhow::{bail, Context, Result};
-use std::collections::VecDeque;
+use anyhow::{bail, Result};
 
 mod conditional;
 use conditional::Conditional;
 mod function;
 use function::Function;
 mod variable;
-use variable::Assignment;
+use variable::{Assignment, Reassignment};
+mod whileloop;
+use whileloop::WhileLoop;
 
 #[derive(Debug)]
 pub enum Statement {
     Return(Option<Expression>),
     Function(Function),
     VariableDeclaration(Assignment),
+    Reassignment(Reassignment),
+    /// An expression run for its side effects, like a call to a void function.
+    Expression(Expression),
     Conditional(Conditional),
+    While(WhileLoop),
+    Empty,
 }
 
 impl ASTNode for Statement {
-    fn new(lexemes: &mut VecDeque<Lexeme>, symtab: &mut SymbolTable) -> Result<Self> {
-        Ok(match lexemes.front().context("Unexpected EOF")? {
+    fn new(lexemes: &mut Lexemes, symtab: &mut SymbolTable) -> Result<Self> {
+        Ok(match lexemes.front_or_eof()? {
             Lexeme::Keyword(Keyword::Fn) => Self::Function(Function::new(lexemes, symtab)?),
             Lexeme::Keyword(Keyword::Return) => {
                 consume!(Lexeme::Keyword(Keyword::Return) in lexemes)?;
-                let expr = if matches!(lexemes.front().context("Unexpected EOF")?, Lexeme::Newline)
-                {
+                let expr = if matches!(lexemes.front_or_eof()?, Lexeme::Newline) {
                     None
                 } else {
                     Some(Expression::new(lexemes, symtab)?)
@@ -40,14 +45,32 @@ impl ASTNode for Statement {
                 consume!(Lexeme::Newline in lexemes)?;
                 Self::Return(expr)
             }
-            Lexeme::Keyword(Keyword::Variable) | Lexeme::Keyword(Keyword::Modifier(_)) => {
+            Lexeme::Keyword(Keyword::Variable)
+            | Lexeme::Keyword(Keyword::Let)
+            | Lexeme::Keyword(Keyword::Modifier(_)) => {
                 Self::VariableDeclaration(Assignment::new(lexemes, symtab)?)
             }
             Lexeme::Keyword(Keyword::If) => Self::Conditional(Conditional::new(lexemes, symtab)?),
-            unexpected => todo!(
-                "token encountered: {:?}; all tokens\n{:?}",
-                unexpected,
-                lexemes
+            Lexeme::Keyword(Keyword::While) => Self::While(WhileLoop::new(lexemes, symtab)?),
+            Lexeme::Idn(_) if lexemes.get(1) == Some(&Lexeme::Equals) => {
+                Self::Reassignment(Reassignment::new(lexemes, symtab)?)
+            }
+            Lexeme::Idn(_) if lexemes.get(1) == Some(&Lexeme::OpenParen) => {
+                let expr = Expression::new(lexemes, symtab)?;
+                consume!(Lexeme::Newline in lexemes)?;
+                Self::Expression(expr)
+            }
+            Lexeme::Newline => {
+                consume!(Lexeme::Newline in lexemes)?;
+                Self::Empty
+            }
+            unexpected => bail!(
+                "Expected a statement, found {}",
+                match unexpected {
+                    Lexeme::Literal(lit) => lit.describe(),
+                    Lexeme::Idn(name) => format!("`{name}`"),
+                    other => format!("{other:?}"),
+                }
             ),
         })
     }
@@ -79,10 +102,23 @@ impl ASTNode for Statement {
                 ass.codegen(ow, symtab);
                 "".to_string()
             }
+            Statement::Reassignment(ass) => {
+                ass.codegen(ow, symtab);
+                "".to_string()
+            }
             Statement::Conditional(cnd) => {
                 cnd.codegen(ow, symtab);
                 "".to_string()
             }
+            Statement::Expression(expr) => {
+                expr.codegen(ow, symtab);
+                "".to_string()
+            }
+            Statement::While(wl) => {
+                wl.codegen(ow, symtab);
+                "".to_string()
+            }
+            Statement::Empty => "".to_string(),
         };
         ow.appendln(stmt, 1);
     }
@@ -93,17 +129,89 @@ pub struct Block {
     pub stmts: Vec<Statement>,
 }
 
+impl Block {
+    /// Checks every `return` in the block, including those in nested blocks,
+    /// gives a value of type `expected`.
+    pub fn check_returns(&self, expected: PrimitiveType, symtab: &mut SymbolTable) -> Result<()> {
+        for stmt in &self.stmts {
+            match stmt {
+                Statement::Return(expr) => {
+                    let found = match expr {
+                        Some(expr) => expr.evaltype(symtab)?,
+                        None => PrimitiveType::Void,
+                    };
+                    if found != expected {
+                        bail!(
+                            "Returned a value of type {found}, but the function returns {expected}"
+                        );
+                    }
+                }
+                Statement::Conditional(cnd) => {
+                    for block in cnd.blocks() {
+                        block.check_returns(expected, symtab)?;
+                    }
+                }
+                Statement::While(wl) => wl.body.check_returns(expected, symtab)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// The functions called anywhere in the block, in the order they appear.
+    pub fn calls(&self) -> Vec<String> {
+        self.stmts
+            .iter()
+            .flat_map(|stmt| match stmt {
+                Statement::Return(expr) => expr.iter().flat_map(|expr| expr.calls()).collect(),
+                Statement::VariableDeclaration(ass) => {
+                    ass.value.iter().flat_map(|expr| expr.calls()).collect()
+                }
+                Statement::Reassignment(ass) => ass.value.calls(),
+                Statement::Expression(expr) => expr.calls(),
+                Statement::Conditional(cnd) => cnd.calls(),
+                Statement::While(wl) => {
+                    let mut calls = wl.cond.calls();
+                    calls.extend(wl.body.calls());
+                    calls
+                }
+                Statement::Function(_) | Statement::Empty => vec![],
+            })
+            .collect()
+    }
+
+    /// Whether every path through the block ends in a `return`.
+    pub fn always_returns(&self) -> bool {
+        self.stmts.iter().any(|stmt| match stmt {
+            Statement::Return(_) => true,
+            Statement::Conditional(cnd) => cnd.always_returns(),
+            _ => false,
+        })
+    }
+}
+
 impl ASTNode for Block {
-    fn new(lexemes: &mut VecDeque<Lexeme>, symtab: &mut SymbolTable) -> Result<Self> {
+    fn new(lexemes: &mut Lexemes, symtab: &mut SymbolTable) -> Result<Self> {
         let mut node = Self::default();
 
+        let opened = lexemes.pos();
         consume!(Lexeme::OpenBrace in lexemes)?;
+        // variables declared in the block go out of scope at its end; the
+        // declaration counts are kept, so they never reuse an alloca's name
+        let outer = symtab.vars.clone();
         while !lexemes.is_empty() {
             if let Some(Lexeme::CloseBrace) = lexemes.front() {
                 break;
             }
             node.stmts.push(Statement::new(lexemes, symtab)?);
         }
+        symtab.vars = outer;
+        if lexemes.is_empty() {
+            bail!(
+                "Unclosed `{{` at {opened}: the file ended at {} before the block's closing `}}`",
+                lexemes.pos()
+            );
+        }
         consume!(Lexeme::CloseBrace in lexemes)?;
         Ok(node)
     }
@@ -120,8 +228,21 @@ pub struct Root {
     pub stmts: Vec<Statement>,
 }
 
+impl Root {
+    /// Every function's name, with the functions its body calls.
+    pub fn calls(&self) -> Vec<(&str, Vec<String>)> {
+        self.stmts
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::Function(func) => Some((func.name.as_str(), func.body.calls())),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
 impl ASTNode for Root {
-    fn new(lexemes: &mut VecDeque<Lexeme>, symtab: &mut SymbolTable) -> Result<Self> {
+    fn new(lexemes: &mut Lexemes, symtab: &mut SymbolTable) -> Result<Self> {
         let mut node = Self::default();
 
         while !lexemes.is_empty() {
@@ -140,3 +261,26 @@ impl ASTNode for Root {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lex::{lex, LexConfig};
+
+    #[test]
+    fn unclosed_block_points_at_its_brace() {
+        let mut lexemes = lex(
+            "fn int main() {\n    return 1;\n}".to_string(),
+            &LexConfig::default(),
+        )
+        .unwrap();
+        // the lexer rejects unbalanced braces itself, so cut the `}` off after
+        lexemes.pop_back();
+        let err = Root::new(&mut lexemes, &mut SymbolTable::default()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unclosed `{` at line 1, column 15: the file ended at line 3, column 1 \
+             before the block's closing `}`"
+        );
+    }
+}
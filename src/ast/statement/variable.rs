@@ -4,41 +4,57 @@ use crate::ast::{
     ASTNode, PrimitiveType, SymbolTable, Var,
 };
 use crate::consume;
-use crate::lex::{Keyword, Lexeme, Modifier};
+use crate::lex::{Keyword, Lexeme, Lexemes, Modifier};
 use anyhow::{bail, Context, Result};
-use std::collections::VecDeque;
 
 #[derive(Debug, Default)]
 pub struct Assignment {
     pub modifiers: Vec<Modifier>,
     pub name: String,
+    pub ir_name: String,
     pub value: Option<Expression>,
     pub vtype: Option<PrimitiveType>,
 }
 
 impl ASTNode for Assignment {
-    fn new(lexemes: &mut VecDeque<Lexeme>, symtab: &mut SymbolTable) -> Result<Self> {
+    fn new(lexemes: &mut Lexemes, symtab: &mut SymbolTable) -> Result<Self> {
         let mut node = Self::default();
-        while let Lexeme::Keyword(Keyword::Modifier(modi)) =
-            lexemes.pop_front().context("Unexpcted EOF")?
-        {
-            node.modifiers.push(modi);
-        }
-
-        consume!(Lexeme::Idn(nm) in lexemes => {
-            node.name = nm;
-        })?;
+        let keyword = loop {
+            match lexemes.pop_front_or_eof()? {
+                Lexeme::Keyword(Keyword::Modifier(modi)) => node.modifiers.push(modi),
+                keyword => break keyword,
+            }
+        };
 
-        if let Lexeme::Keyword(Keyword::Coercion) = lexemes.front().context("Unexpected EOF")? {
-            consume!(Lexeme::Keyword(Keyword::Coercion) in lexemes)?;
-            consume!(Lexeme::Idn(ty) in lexemes => {
-                node.vtype = Some(PrimitiveType::from_str(ty)?);
+        if keyword == Lexeme::Keyword(Keyword::Let) {
+            // let <type> <name> = <expr>;
+            node.vtype = Some(PrimitiveType::consume(lexemes)?);
+            consume!(Lexeme::Idn(nm) in lexemes => {
+                node.name = nm;
+            })?;
+            if lexemes.front() != Some(&Lexeme::Equals) {
+                bail!(
+                    "`let` declarations need an initialiser, but `{}` has none",
+                    node.name
+                );
+            }
+        } else {
+            consume!(Lexeme::Idn(nm) in lexemes => {
+                node.name = nm;
             })?;
+
+            if let Lexeme::Keyword(Keyword::Coercion) = lexemes.front_or_eof()? {
+                consume!(Lexeme::Keyword(Keyword::Coercion) in lexemes)?;
+                node.vtype = Some(PrimitiveType::consume(lexemes)?);
+            }
         }
 
-        node.value = match lexemes.pop_front().context("Unexpected EOF")? {
+        node.value = match lexemes.pop_front_or_eof()? {
             Lexeme::Newline => None,
             Lexeme::Equals => {
+                if lexemes.front() == Some(&Lexeme::Newline) {
+                    bail!("Missing the initialiser for `{}` after the `=`", node.name);
+                }
                 let expr = Expression::new(lexemes, symtab)?;
                 consume!(Lexeme::Newline in lexemes)?;
                 Some(expr)
@@ -51,27 +67,50 @@ impl ASTNode for Assignment {
             bail!("Either a specified type or initaliser must be present.");
         }
 
-        symtab.insert(
-            node.name.clone(),
-            Var {
-                name: node.name.clone(),
-                vtype: node.vtype,
-                value: node.value.clone(),
-            },
-        );
+        // `void` has no values, so there'd be nothing to store
+        if node.vtype == Some(PrimitiveType::Void) {
+            bail!("Variable `{}` can't have type void", node.name);
+        }
+        if let Some(expr) = &node.value {
+            let found = expr.evaltype(symtab)?;
+            if found == PrimitiveType::Void {
+                bail!(
+                    "The initialiser for `{}` doesn't produce a value",
+                    node.name
+                );
+            }
+            if let Some(vtype) = node.vtype.filter(|vtype| *vtype != found) {
+                bail!(
+                    "`{}` is declared as {vtype}, but initialised with a {found}; convert it with `as`",
+                    node.name
+                );
+            }
+        }
+
+        let vtype = match (node.vtype, &node.value) {
+            (Some(vtype), _) => vtype,
+            (None, value) => value.as_ref().unwrap().evaltype(symtab)?,
+        };
+        node.ir_name = Var {
+            name: node.name.clone(),
+            ir_name: node.name.clone(),
+            vtype,
+            value: node.value.clone(),
+            mutable: node.modifiers.contains(&Modifier::Mutable),
+        }
+        .declare(symtab);
 
         Ok(node)
     }
 
     fn codegen(&self, ow: &mut OutputWrapper, symtab: &mut SymbolTable) {
-        let ty = if self.vtype.is_none() {
-            // None first because borrow checker :/
-            self.value.as_ref().unwrap().evaltype(symtab).unwrap()
+        let ty = if let Some(vtype) = self.vtype {
+            vtype
         } else {
-            self.vtype.unwrap()
+            self.value.as_ref().unwrap().evaltype(symtab).unwrap()
         };
 
-        ow.appendln(format!("%{} = alloca {}", self.name, ty.ir_type()), 1);
+        ow.appendln(format!("%{} = alloca {}", self.ir_name, ty.ir_type()), 1);
         if let Some(val) = &self.value {
             val.codegen(ow, symtab);
             ow.appendln(
@@ -79,10 +118,124 @@ impl ASTNode for Assignment {
                     "store {} {}, ptr %{}",
                     ty.ir_type(),
                     val.eval(symtab).unwrap(),
-                    self.name,
+                    self.ir_name,
                 ),
                 1,
             );
         }
     }
 }
+
+/// `<name> = <expr>;`, storing a new value in a `mut` variable.
+#[derive(Debug)]
+pub struct Reassignment {
+    pub ir_name: String,
+    pub value: Expression,
+    pub vtype: PrimitiveType,
+}
+
+impl ASTNode for Reassignment {
+    fn new(lexemes: &mut Lexemes, symtab: &mut SymbolTable) -> Result<Self> {
+        let name: String;
+        consume!(Lexeme::Idn(nm) in lexemes => name = nm)?;
+        consume!(Lexeme::Equals in lexemes)?;
+        if lexemes.front() == Some(&Lexeme::Newline) {
+            bail!("Missing the value to assign to `{name}` after the `=`");
+        }
+        let value = Expression::new(lexemes, symtab)?;
+        consume!(Lexeme::Newline in lexemes)?;
+
+        let var = symtab
+            .vars
+            .get(&name)
+            .context(format!("Variable {name} not found in scope"))?;
+        if !var.mutable {
+            bail!("Can't assign to `{name}` as it isn't mutable; declare it with `mut var`");
+        }
+        let (vtype, ir_name) = (var.vtype, var.ir_name.clone());
+        let found = value.evaltype(symtab)?;
+        if found != vtype {
+            bail!("Can't assign a {found} to `{name}`, which is {vtype}");
+        }
+
+        Ok(Self {
+            ir_name,
+            value,
+            vtype,
+        })
+    }
+
+    fn codegen(&self, ow: &mut OutputWrapper, symtab: &mut SymbolTable) {
+        self.value.codegen(ow, symtab);
+        ow.appendln(
+            format!(
+                "store {} {}, ptr %{}",
+                self.vtype.ir_type(),
+                self.value.eval(symtab).unwrap(),
+                self.ir_name,
+            ),
+            1,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::parse_src;
+
+    #[test]
+    fn mut_variables_can_be_reassigned() {
+        assert!(parse_src("fn int main() { mut var x = 1; x = 2; return x; }").is_ok());
+    }
+
+    #[test]
+    fn immutable_variables_cant_be_reassigned() {
+        let err = parse_src("fn int main() { var x = 1; x = 2; return x; }").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Can't assign to `x` as it isn't mutable; declare it with `mut var`"
+        );
+    }
+
+    #[test]
+    fn reassignment_needs_a_value() {
+        let err = parse_src("fn int main() { mut var x = 1; x = ; return x; }").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Missing the value to assign to `x` after the `=`"
+        );
+    }
+
+    #[test]
+    fn void_call_cant_initialise_a_variable() {
+        let err =
+            parse_src("fn void f() { } fn int main() { var x = f(); return 0; }").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The initialiser for `x` doesn't produce a value"
+        );
+    }
+
+    #[test]
+    fn let_needs_an_initialiser() {
+        let err = parse_src("fn int main() { let int x; return 0; }").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`let` declarations need an initialiser, but `x` has none"
+        );
+        let err = parse_src("fn int main() { let int x = ; return 0; }").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Missing the initialiser for `x` after the `=`"
+        );
+    }
+
+    #[test]
+    fn let_type_must_match_its_initialiser() {
+        let err = parse_src("fn int main() { let int x = 1.5; return 0; }").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`x` is declared as int, but initialised with a float; convert it with `as`"
+        );
+    }
+}
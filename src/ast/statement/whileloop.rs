@@ -0,0 +1,55 @@
+use crate::ast::{
+    expression::{Expression, ExpressionTrait},
+    statement::Block,
+    ASTNode, OutputWrapper, PrimitiveType, SymbolTable,
+};
+use crate::consume;
+use crate::lex::{Keyword, Lexeme, Lexemes};
+use anyhow::{bail, Result};
+
+/// `while <cond> { ... }`, running the body for as long as `cond` holds.
+#[derive(Debug)]
+pub struct WhileLoop {
+    pub cond: Expression,
+    pub body: Block,
+}
+
+impl ASTNode for WhileLoop {
+    fn new(lexemes: &mut Lexemes, symtab: &mut SymbolTable) -> Result<Self> {
+        consume!(Lexeme::Keyword(Keyword::While) in lexemes)?;
+        if let Some(Lexeme::OpenBrace) = lexemes.front() {
+            bail!("Expected a condition after `while`, before its `{{`");
+        }
+        let cond = Expression::new(lexemes, symtab)?;
+        let ctype = cond.evaltype(symtab)?;
+        if ctype != PrimitiveType::Bool {
+            bail!("The condition of a `while` must be a bool, not {ctype}");
+        }
+
+        Ok(Self {
+            cond,
+            body: Block::new(lexemes, symtab)?,
+        })
+    }
+
+    fn codegen(&self, ow: &mut OutputWrapper, symtab: &mut SymbolTable) {
+        // the condition gets its own block, so the body can jump back to it
+        let (check, body, end) = (ow.names.label(), ow.names.label(), ow.names.label());
+        ow.appendln(format!("br label %{check}"), 1);
+        ow.appendln(format!("{check}:"), 0);
+        self.cond.codegen(ow, symtab);
+        ow.appendln(
+            format!(
+                "br i1 {}, label %{body}, label %{end}",
+                self.cond.ir(symtab)
+            ),
+            1,
+        );
+        ow.appendln(format!("{body}:"), 0);
+        self.body.codegen(ow, symtab);
+        if !self.body.always_returns() {
+            ow.appendln(format!("br label %{check}"), 1);
+        }
+        ow.appendln(format!("{end}:"), 0);
+    }
+}
@@ -0,0 +1,86 @@
+//! Errors that know where in the source they happened. Only syntax errors,
+//! where the lexemes don't fit the grammar, are typed so far; the checks
+//! made once something has parsed (types, scopes, signatures) are still
+//! plain `anyhow` messages. Typed errors travel inside `anyhow::Error`, so
+//! callers can `downcast_ref::<ShadowError>()` to get at them.
+
+use crate::lex::PosInfo;
+use std::fmt;
+use thiserror::Error;
+
+/// Ways the lexemes can fail to fit the grammar.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseErrors {
+    #[error("Unexpected EOF")]
+    UnexpectedEof,
+    #[error("Expected {expected}, got {got}")]
+    UnexpectedToken { expected: String, got: String },
+    #[error("'Custom' variable types not implemented yet (given {0})")]
+    UnknownType(String),
+}
+
+/// Which stage of the compiler an error came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrType {
+    Parse(ParseErrors),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShadowError {
+    pub kind: ErrType,
+    pub pos: PosInfo,
+}
+
+impl ShadowError {
+    pub fn parse(err: ParseErrors, pos: PosInfo) -> Self {
+        Self {
+            kind: ErrType::Parse(err),
+            pos,
+        }
+    }
+
+    /// The error followed by the line of `src` it's on, with a `^` under the
+    /// offending lexeme. Errors at the very end of the file point just past
+    /// its last character.
+    pub fn verbose(&self, src: &str) -> String {
+        let line = src.lines().nth(self.pos.line as usize - 1).unwrap_or("");
+        let gutter = self.pos.line.to_string();
+        format!(
+            "{self}\n{gutter} | {line}\n{} | {}^",
+            " ".repeat(gutter.len()),
+            " ".repeat(self.pos.column as usize - 1)
+        )
+    }
+}
+
+impl fmt::Display for ShadowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            ErrType::Parse(err) => write!(f, "[SDW E/P] {}: {err}", self.pos),
+        }
+    }
+}
+
+impl std::error::Error for ShadowError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verbose_marks_the_offending_lexeme() {
+        let err = ShadowError::parse(
+            ParseErrors::UnexpectedEof,
+            PosInfo {
+                line: 2,
+                column: 11,
+            },
+        );
+        assert_eq!(
+            err.verbose("fn int main() {\n    return\n}"),
+            "[SDW E/P] line 2, column 11: Unexpected EOF\n\
+             2 |     return\n  \
+               |           ^"
+        );
+    }
+}
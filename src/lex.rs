@@ -1,4 +1,6 @@
+use crate::error::{ParseErrors, ShadowError};
 use anyhow::{bail, Context, Result};
+use std::{collections::VecDeque, iter::Peekable, str::CharIndices};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Modifier {
@@ -11,10 +13,12 @@ pub enum Keyword {
     Fn,
     Return,
     Variable,
+    Let,
     Modifier(Modifier),
     Coercion,
     If,
     Else,
+    While,
 }
 
 impl Keyword {
@@ -23,22 +27,45 @@ impl Keyword {
             "fn" => Keyword::Fn,
             "return" => Keyword::Return,
             "var" => Keyword::Variable,
+            "let" => Keyword::Let,
             "as" => Keyword::Coercion,
             "mut" => Keyword::Modifier(Modifier::Mutable),
             "dyn" => Keyword::Modifier(Modifier::Dynamic),
             "if" => Keyword::If,
             "else" => Keyword::Else,
+            "while" => Keyword::While,
             _ => bail!("Unknown keyword parsed, '{from}'"),
         })
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Literal {
     Integer(i64), // TODO: Add support for negative numbers
+    /// An integer too large for an `Integer`, only read with
+    /// `LexConfig::wide_literals`.
+    WideInteger(i128),
+    Float(f64),
+    Str(String),
+    Char(char),
+    Bool(bool),
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+impl Literal {
+    /// What kind of literal this is, and how it reads, for error messages.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Integer(int) => format!("integer literal `{int}`"),
+            Self::WideInteger(int) => format!("integer literal `{int}`"),
+            Self::Float(float) => format!("float literal `{float}`"),
+            Self::Str(string) => format!("string literal `{string:?}`"),
+            Self::Char(ch) => format!("character literal `{ch:?}`"),
+            Self::Bool(bool) => format!("bool literal `{bool}`"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum Lexeme {
     Keyword(Keyword),
     Idn(String),
@@ -55,30 +82,542 @@ pub enum Lexeme {
     Subtraction,
     Multiplication,
     Division,
+    Modulo,
     AngleLeft,
     AngleRight,
+    DoubleEquals,
+    BangEquals,
+    AngleLeftEquals,
+    AngleRightEquals,
+    Arrow,
 }
 
+/// Every symbol the lexer knows. Longer symbols come first, so the longest
+/// match wins and `==` isn't read as two `=`.
+/// `{` isn't here, as it needs its position.
+const SYMBOLS: [(&str, Lexeme); 20] = [
+    ("->", Lexeme::Arrow),
+    ("==", Lexeme::DoubleEquals),
+    ("!=", Lexeme::BangEquals),
+    ("<=", Lexeme::AngleLeftEquals),
+    (">=", Lexeme::AngleRightEquals),
+    ("{", Lexeme::OpenBrace),
+    ("}", Lexeme::CloseBrace),
+    ("(", Lexeme::OpenParen),
+    (")", Lexeme::CloseParen),
+    (";", Lexeme::Newline),
+    (",", Lexeme::Delimiter),
+    ("=", Lexeme::Equals),
+    ("!", Lexeme::Bang),
+    ("+", Lexeme::Addition),
+    ("-", Lexeme::Subtraction),
+    ("*", Lexeme::Multiplication),
+    ("/", Lexeme::Division),
+    ("%", Lexeme::Modulo),
+    ("<", Lexeme::AngleLeft),
+    (">", Lexeme::AngleRight),
+];
+
 impl Lexeme {
-    fn from_char(from: char) -> Result<Self> {
-        Ok(match from {
-            '{' => Lexeme::OpenBrace,
-            '}' => Lexeme::CloseBrace,
-            '(' => Lexeme::OpenParen,
-            ')' => Lexeme::CloseParen,
-            ';' => Lexeme::Newline,
-            ',' => Lexeme::Delimiter,
-            '=' => Lexeme::Equals,
-            '!' => Lexeme::Bang,
-            '+' => Lexeme::Addition,
-            '-' => Lexeme::Subtraction,
-            '*' => Lexeme::Multiplication,
-            '/' => Lexeme::Division,
-            '<' => Lexeme::AngleLeft,
-            '>' => Lexeme::AngleRight,
-            _ => bail!("Unknown symbol '{from}' encountered."),
-        })
+    /// The longest symbol at the start of `from`, and how many bytes it takes up.
+    fn symbol(from: &str) -> Option<(Self, usize)> {
+        SYMBOLS
+            .iter()
+            .find(|(symbol, _)| from.starts_with(symbol))
+            .map(|(symbol, lexeme)| (lexeme.clone(), symbol.len()))
+    }
+}
+
+/// The lexer's output: every lexeme along with where it starts, so the
+/// parser can say where an error is. It's read from the front, like a
+/// `VecDeque`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lexemes {
+    lexemes: VecDeque<(Lexeme, PosInfo)>,
+    /// Just past the end of the file, for errors that run into it.
+    end: PosInfo,
+}
+
+impl Default for Lexemes {
+    fn default() -> Self {
+        Self {
+            lexemes: VecDeque::new(),
+            end: PosInfo { line: 1, column: 1 },
+        }
+    }
+}
+
+impl Lexemes {
+    pub fn push_back(&mut self, lexeme: Lexeme, pos: PosInfo) {
+        self.lexemes.push_back((lexeme, pos));
+    }
+
+    pub fn pop_front(&mut self) -> Option<Lexeme> {
+        self.lexemes.pop_front().map(|(lexeme, _)| lexeme)
+    }
+
+    /// Takes the last lexeme off, so the lexemes end where it started.
+    pub fn pop_back(&mut self) -> Option<Lexeme> {
+        let (lexeme, pos) = self.lexemes.pop_back()?;
+        self.end = pos;
+        Some(lexeme)
+    }
+
+    pub fn front(&self) -> Option<&Lexeme> {
+        self.get(0)
+    }
+
+    /// Like `front`, but running out of lexemes is an error at the end of
+    /// the file.
+    pub fn front_or_eof(&self) -> Result<&Lexeme> {
+        self.front().ok_or_else(|| self.eof())
+    }
+
+    /// Like `pop_front`, but running out of lexemes is an error at the end
+    /// of the file.
+    pub fn pop_front_or_eof(&mut self) -> Result<Lexeme> {
+        self.pop_front().ok_or_else(|| self.eof())
+    }
+
+    fn eof(&self) -> anyhow::Error {
+        ShadowError::parse(ParseErrors::UnexpectedEof, self.end).into()
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&Lexeme> {
+        self.lexemes.get(idx).map(|(lexeme, _)| lexeme)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lexemes.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lexemes.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Lexeme> {
+        self.lexemes.iter().map(|(lexeme, _)| lexeme)
+    }
+
+    /// Where the front lexeme starts, or the end of the file once they've
+    /// all been read.
+    pub fn pos(&self) -> PosInfo {
+        self.lexemes.front().map_or(self.end, |&(_, pos)| pos)
+    }
+
+    /// Splits the lexemes before every index in `at`, which must be in
+    /// order. Each part ends where the next one starts, so an error at the
+    /// end of one points at the start of the next.
+    pub fn split_before(mut self, at: &[usize]) -> Vec<Self> {
+        let mut parts = vec![];
+        for &idx in at.iter().rev() {
+            let rest = self.lexemes.split_off(idx);
+            let start = rest.front().map_or(self.end, |&(_, pos)| pos);
+            let end = std::mem::replace(&mut self.end, start);
+            parts.push(Self { lexemes: rest, end });
+        }
+        parts.push(self);
+        parts.reverse();
+        parts
+    }
+}
+
+impl From<Lexemes> for Vec<Lexeme> {
+    fn from(lexemes: Lexemes) -> Self {
+        lexemes
+            .lexemes
+            .into_iter()
+            .map(|(lexeme, _)| lexeme)
+            .collect()
+    }
+}
+
+/// Knobs for embedding the lexer in other settings.
+#[derive(Debug)]
+pub struct LexConfig {
+    /// Read integer literals as base 16, so `10` is sixteen.
+    pub hex_literals: bool,
+    /// Reject decimal literals like `007`, which read as octal in C.
+    pub reject_leading_zeros: bool,
+    /// Read integer literals too large for an `int` as `i128`s, rather than
+    /// rejecting them.
+    pub wide_literals: bool,
+    /// Markers that start a comment running to the end of the line, `//` by
+    /// default.
+    pub line_comments: Vec<String>,
+    /// The opening and closing markers of block comments, which nest; `/*`
+    /// and `*/` by default. Comment markers are looked for after words and
+    /// literals, so they shouldn't start with a letter, digit or quote.
+    pub block_comments: Vec<(String, String)>,
+}
+
+impl Default for LexConfig {
+    fn default() -> Self {
+        Self {
+            hex_literals: false,
+            reject_leading_zeros: false,
+            wide_literals: false,
+            line_comments: vec!["//".to_string()],
+            block_comments: vec![("/*".to_string(), "*/".to_string())],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PosInfo {
+    pub line: u64,
+    pub column: u64,
+}
+
+impl PosInfo {
+    /// Moves past `ch`, wrapping onto the next line after a newline.
+    fn advance(&mut self, ch: char) {
+        if ch == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+    }
+}
+
+impl std::fmt::Display for PosInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// Pairs every opening delimiter with its closer, giving the positions of
+/// both. Unbalanced delimiters are reported at the offending character, so
+/// an unclosed block points at its `{` rather than an EOF in the parser.
+/// Delimiters inside comments, strings (raw ones included) and characters are
+/// ignored, and an unterminated comment or literal is reported at its opening
+/// marker, quote or `r`.
+pub fn match_delimiters(inp: &str, config: &LexConfig) -> Result<Vec<(PosInfo, PosInfo)>> {
+    let mut open: Vec<(char, PosInfo)> = vec![];
+    let mut pairs = vec![];
+    let mut pos = PosInfo { line: 1, column: 1 };
+    let mut chars = inp.char_indices().peekable();
+    let mut line_comment = false;
+    // block comments nest, so keep the closer and start of every one that's
+    // still open
+    let mut block_comments: Vec<(&str, PosInfo)> = vec![];
+    // the quote that opened the string or character literal we're in
+    let mut quote: Option<(char, PosInfo)> = None;
+
+    while let Some((idx, ch)) = chars.next() {
+        let here = pos;
+        pos.advance(ch);
+        let rest = &inp[idx..];
+
+        if line_comment {
+            line_comment = ch != '\n';
+            continue;
+        }
+        // comment markers inside a string or character are just text
+        if quote.is_none() {
+            if let Some((opener, closer)) = config
+                .block_comments
+                .iter()
+                .find(|(opener, _)| rest.starts_with(opener.as_str()))
+            {
+                skip_to(&mut chars, &mut pos, idx + opener.len());
+                block_comments.push((closer, here));
+                continue;
+            }
+            if let Some(&(closer, _)) = block_comments.last() {
+                if rest.starts_with(closer) {
+                    skip_to(&mut chars, &mut pos, idx + closer.len());
+                    block_comments.pop();
+                }
+                continue;
+            }
+        }
+
+        match (ch, chars.peek().map(|&(_, next)| next)) {
+            _ if quote.is_some_and(|(q, _)| q == ch) => quote = None,
+            ('\\', Some(next)) if quote.is_some() && next != '\n' => {
+                pos.advance(chars.next().unwrap().1)
+            }
+            ('\n', _) => {
+                if let Some((q, start)) = quote {
+                    bail!("Unterminated {} literal opened at {start}", quoted_kind(q));
+                }
+            }
+            _ if quote.is_some() => {}
+            // an `r` ending an identifier, as in `bar"baz"`, doesn't start one
+            ('r', _) if !inp[..idx].ends_with(|c: char| c.is_ascii_alphanumeric() || c == '_') => {
+                if let Some(hashes) = raw_string_hashes(rest) {
+                    let (_, len) = scan_raw(rest, hashes)
+                        .context(format!("Unterminated raw string literal opened at {here}"))?;
+                    skip_to(&mut chars, &mut pos, idx + len);
+                }
+            }
+            ('"' | '\'', _) => quote = Some((ch, here)),
+            _ if config
+                .line_comments
+                .iter()
+                .any(|marker| rest.starts_with(marker.as_str())) =>
+            {
+                line_comment = true
+            }
+            ('{' | '(' | '[', _) => open.push((ch, here)),
+            ('}' | ')' | ']', _) => {
+                let (opener, start) = open.pop().context(format!("Unmatched `{ch}` at {here}"))?;
+                let closer = match opener {
+                    '{' => '}',
+                    '(' => ')',
+                    _ => ']',
+                };
+                if ch != closer {
+                    bail!("Expected `{closer}` to close the `{opener}` opened at {start}, found `{ch}` at {here}");
+                }
+                pairs.push((start, here));
+            }
+            _ => {}
+        }
+    }
+
+    if let Some((_, pos)) = block_comments.first() {
+        bail!("Unterminated block comment opened at {pos}");
+    }
+    if let Some((q, pos)) = quote {
+        bail!("Unterminated {} literal opened at {pos}", quoted_kind(q));
     }
+    if let Some((opener, pos)) = open.pop() {
+        bail!("Unclosed `{opener}` opened at {pos}");
+    }
+    Ok(pairs)
+}
+
+/// Moves `chars` on to byte offset `end`, keeping `pos` in step.
+fn skip_to(chars: &mut Peekable<CharIndices>, pos: &mut PosInfo, end: usize) {
+    while chars.peek().is_some_and(|&(next, _)| next < end) {
+        pos.advance(chars.next().unwrap().1);
+    }
+}
+
+/// The position of the character at byte offset `offset` into `inp`.
+fn position_of(inp: &str, offset: usize) -> PosInfo {
+    let mut pos = PosInfo { line: 1, column: 1 };
+    inp[..offset].chars().for_each(|ch| pos.advance(ch));
+    pos
+}
+
+/// How many bytes the (possibly nested) block comment at the start of `inp`
+/// takes up, including its `closer`.
+fn block_comment_len(inp: &str, opener: &str, closer: &str) -> Result<usize> {
+    let (mut depth, mut len) = (0, 0);
+    let bytes = inp.as_bytes();
+    while len < bytes.len() {
+        if bytes[len..].starts_with(opener.as_bytes()) {
+            depth += 1;
+            len += opener.len();
+        } else if bytes[len..].starts_with(closer.as_bytes()) {
+            depth -= 1;
+            len += closer.len();
+            if depth == 0 {
+                return Ok(len);
+            }
+        } else {
+            len += 1;
+        }
+    }
+    bail!("Unterminated block comment")
+}
+
+fn quoted_kind(quote: char) -> &'static str {
+    if quote == '"' {
+        "string"
+    } else {
+        "character"
+    }
+}
+
+/// Scans the string or character literal at the start of `inp` (which must
+/// begin with its quote), decoding its escapes. Returns the contents and how
+/// many bytes the literal took up, quotes included.
+fn scan_quoted(inp: &str) -> Result<(String, usize)> {
+    let quote = inp.chars().next().unwrap();
+    let mut string = String::new();
+    let mut chars = inp.char_indices().skip(1);
+    while let Some((idx, ch)) = chars.next() {
+        match ch {
+            _ if ch == quote => return Ok((string, idx + 1)),
+            '\n' => break,
+            '\\' => string.push(match chars.next() {
+                Some((_, 'n')) => '\n',
+                Some((_, 't')) => '\t',
+                Some((_, '\\')) => '\\',
+                Some((_, '"')) => '"',
+                Some((_, '\'')) => '\'',
+                Some((_, other)) => bail!(
+                    "Unknown escape sequence '\\{other}' in {} literal",
+                    quoted_kind(quote)
+                ),
+                None => break,
+            }),
+            _ => string.push(ch),
+        }
+    }
+    bail!("Unterminated {} literal", quoted_kind(quote))
+}
+
+/// How many `#`s the raw string at the start of `inp` is opened with (as in
+/// `r#"`), or `None` if `inp` doesn't start with a raw string.
+fn raw_string_hashes(inp: &str) -> Option<usize> {
+    let hashes = inp
+        .strip_prefix('r')?
+        .bytes()
+        .take_while(|&b| b == b'#')
+        .count();
+    (inp.as_bytes().get(hashes + 1) == Some(&b'"')).then_some(hashes)
+}
+
+/// Scans the raw string at the start of `inp`, opened with `hashes` `#`s. Its
+/// contents are kept verbatim, escapes and newlines included, up to a quote
+/// followed by as many `#`s, so `r#"say "hi""#` holds `say "hi"`. Returns the
+/// contents and how many bytes the literal took up, or `None` if it's never
+/// closed.
+fn scan_raw(inp: &str, hashes: usize) -> Option<(String, usize)> {
+    let start = hashes + 2;
+    let terminator = format!("\"{}", "#".repeat(hashes));
+    let len = inp[start..].find(&terminator)?;
+    Some((
+        inp[start..start + len].to_string(),
+        start + len + terminator.len(),
+    ))
+}
+
+/// Scans the number literal at the start of `inp` (which must begin with a
+/// decimal digit), returning it and how many characters it took up.
+///
+/// One pass handles `0x`/`0o`/`0b` prefixes, `_` separators and the
+/// leading-zero policy. Literals always start with a decimal digit, so with
+/// hex literals `ff` is still an identifier whilst `0ff` is 255; explicit
+/// prefixes take priority over the default radix.
+///
+/// A decimal literal with a fraction or exponent is a float. A trailing dot
+/// (`5.`) still makes a float, unless an identifier follows it, so `1.foo`
+/// is left as the integer `1` for a member access.
+///
+/// `at` gives the position of the character at a byte offset into `inp`, and
+/// is only called once there's an error to report.
+fn scan_number(
+    inp: &str,
+    config: &LexConfig,
+    at: impl Fn(usize) -> PosInfo,
+) -> Result<(Literal, usize)> {
+    let chars: Vec<char> = inp
+        .chars()
+        .take_while(|ch| ch.is_ascii_alphanumeric() || "_.+-".contains(*ch))
+        .collect();
+
+    let (radix, prefix) = match (chars[0], chars.get(1)) {
+        ('0', Some('x')) => (16, 2),
+        ('0', Some('o')) => (8, 2),
+        ('0', Some('b')) => (2, 2),
+        _ => (if config.hex_literals { 16 } else { 10 }, 0),
+    };
+
+    let starts_exponent = |at: usize| match chars.get(at + 1) {
+        Some('+' | '-') => chars.get(at + 2).is_some_and(char::is_ascii_digit),
+        next => next.is_some_and(char::is_ascii_digit),
+    };
+
+    let mut digits = String::new();
+    let (mut len, mut float, mut exponent) = (prefix, false, false);
+    while let Some(&ch) = chars.get(len) {
+        match ch {
+            '_' => {}
+            _ if ch.is_digit(radix) => digits.push(ch),
+            '.' if radix == 10
+                && !float
+                && !chars
+                    .get(len + 1)
+                    .is_some_and(|next| next.is_ascii_alphabetic() || "_.".contains(*next)) =>
+            {
+                float = true;
+                digits.push(ch);
+            }
+            'e' | 'E' if radix == 10 && !exponent && starts_exponent(len) => {
+                (float, exponent) = (true, true);
+                digits.push(ch);
+                if let Some(&sign @ ('+' | '-')) = chars.get(len + 1) {
+                    digits.push(sign);
+                    len += 1;
+                }
+            }
+            '.' | '+' | '-' => break,
+            // everything before here is ascii, so `len` is a byte offset too
+            _ => bail!(
+                "Invalid digit '{ch}' in base {radix} literal '{}' at {}",
+                chars[..=len].iter().collect::<String>(),
+                at(len)
+            ),
+        }
+        len += 1;
+    }
+
+    let literal: String = chars[..len].iter().collect();
+    if digits.is_empty() {
+        bail!("Expected digits after the '{literal}' prefix at {}", at(0));
+    }
+    if float {
+        let float = digits
+            .parse::<f64>()
+            .with_context(|| format!("Invalid float literal '{literal}' at {}", at(0)))?;
+        // constants stay finite; infinities and NaN only come from runtime
+        // arithmetic, which follows IEEE
+        if !float.is_finite() {
+            bail!(
+                "Float literal '{literal}' at {} is too large to be represented",
+                at(0)
+            );
+        }
+        return Ok((Literal::Float(float), len));
+    }
+    if config.reject_leading_zeros
+        && prefix == 0
+        && radix == 10
+        && digits.len() > 1
+        && digits.starts_with('0')
+    {
+        let trimmed = match digits.trim_start_matches('0') {
+            "" => "0",
+            trimmed => trimmed,
+        };
+        // only suggest octal when the digits could be, unlike `009`
+        let octal = if trimmed.chars().all(|ch| ('0'..='7').contains(&ch)) {
+            format!(", or `0o{trimmed}` if you meant octal")
+        } else {
+            String::new()
+        };
+        bail!(
+            "Decimal literal '{literal}' at {} has leading zeros; write it as `{trimmed}`{octal}",
+            at(0)
+        );
+    }
+
+    // the digits are all valid by now, so parsing can only overflow
+    if let Ok(int) = i64::from_str_radix(&digits, radix) {
+        return Ok((Literal::Integer(int), len));
+    }
+    if !config.wide_literals {
+        bail!(
+            "Integer literal '{literal}' at {} is too large for an int, which holds at most {}",
+            at(0),
+            i64::MAX
+        );
+    }
+    let wide = i128::from_str_radix(&digits, radix).with_context(|| {
+        format!(
+            "Integer literal '{literal}' at {} is too large even for an i128, which holds at most {}",
+            at(0),
+            i128::MAX
+        )
+    })?;
+    Ok((Literal::WideInteger(wide), len))
 }
 
 struct LexBuffer {
@@ -99,10 +638,6 @@ impl LexBuffer {
         self.at(self.idx)
     }
 
-    fn peek(&self) -> Result<char> {
-        self.at(self.idx + 1)
-    }
-
     fn empty(&self) -> bool {
         self.inp.is_empty()
     }
@@ -122,44 +657,303 @@ impl LexBuffer {
     }
 }
 
-pub fn lex(inp: String) -> Result<Vec<Lexeme>> {
-    let mut buf = LexBuffer { inp, idx: 0 };
-    let mut lexemes: Vec<Lexeme> = vec![];
+pub fn lex(inp: String, config: &LexConfig) -> Result<Lexemes> {
+    // skip a byte order mark and a `#!` interpreter line; the shebang's
+    // newline is kept so line numbers still match the file
+    let mut inp = inp.strip_prefix('\u{feff}').unwrap_or(&inp);
+    if inp.starts_with("#!") {
+        inp = &inp[inp.find('\n').unwrap_or(inp.len())..];
+    }
+
+    match_delimiters(inp, config)?;
+    let mut buf = LexBuffer {
+        inp: inp.to_string(),
+        idx: 0,
+    };
+    let mut lexemes = Lexemes::default();
+    // where the lexeme about to be scanned starts, worked out from the last
+    // one rather than the start of the file each time
+    let (mut pos, mut scanned) = (PosInfo { line: 1, column: 1 }, 0);
 
     while !buf.empty() {
-        while buf.get()?.is_ascii_alphabetic() {
-            if !buf.peek()?.is_ascii_alphabetic() {
-                let kw_idn = buf.inp[..(buf.idx as usize) + 1].to_string();
-                buf.trim(buf.idx + 1)?;
-                let lexeme = if let Ok(kw) = Keyword::from_str(&kw_idn) {
-                    Lexeme::Keyword(kw)
-                } else {
-                    Lexeme::Idn(kw_idn)
-                };
-                lexemes.push(lexeme);
-                break;
-            }
-            buf.next();
+        let offset = inp.len() - buf.inp.len();
+        inp[scanned..offset].chars().for_each(|ch| pos.advance(ch));
+        scanned = offset;
+
+        // checked before identifiers, which would take the `r`
+        if let Some(hashes) = raw_string_hashes(&buf.inp) {
+            let (string, len) =
+                scan_raw(&buf.inp, hashes).context("Unterminated raw string literal")?;
+            buf.trim(len as i64)?;
+            lexemes.push_back(Lexeme::Literal(Literal::Str(string)), pos);
+            continue;
         }
 
-        while buf.get()?.is_ascii_digit() {
-            if !buf.peek()?.is_ascii_digit() {
-                let numlit = buf.inp[..(buf.idx as usize) + 1].to_string();
-                buf.trim(buf.idx + 1)?;
-                let lexeme = Lexeme::Literal(Literal::Integer(numlit.parse().unwrap()));
-                lexemes.push(lexeme);
-                break;
-            }
-            buf.next();
+        // words start with a letter, but can go on with digits and
+        // underscores, as in `i32` or `my_var`
+        if buf.get()?.is_ascii_alphabetic() {
+            let len = buf
+                .inp
+                .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_'))
+                .unwrap_or(buf.inp.len());
+            let kw_idn = buf.inp[..len].to_string();
+            buf.trim(len as i64)?;
+            // matched on the whole word, so `trueish` stays an identifier
+            let lexeme = if let Ok(kw) = Keyword::from_str(&kw_idn) {
+                Lexeme::Keyword(kw)
+            } else if let Ok(bool) = kw_idn.parse() {
+                Lexeme::Literal(Literal::Bool(bool))
+            } else {
+                Lexeme::Idn(kw_idn)
+            };
+            lexemes.push_back(lexeme, pos);
+            continue;
         }
 
-        if let Ok(lexeme) = Lexeme::from_char(buf.get()?) {
-            lexemes.push(lexeme);
+        if buf.get()?.is_ascii_digit() {
+            let (literal, len) = scan_number(&buf.inp, config, |at| position_of(inp, offset + at))?;
+            buf.trim(len as i64)?;
+            lexemes.push_back(Lexeme::Literal(literal), pos);
+            continue;
+        }
+
+        if buf.inp.starts_with('"') {
+            let (string, len) = scan_quoted(&buf.inp)?;
+            buf.trim(len as i64)?;
+            lexemes.push_back(Lexeme::Literal(Literal::Str(string)), pos);
+            continue;
+        }
+
+        if buf.inp.starts_with('\'') {
+            let (string, len) = scan_quoted(&buf.inp)?;
+            let mut chars = string.chars();
+            let ch = match (chars.next(), chars.next()) {
+                (Some(ch), None) => ch,
+                (None, _) => bail!("Empty character literal ''"),
+                _ => bail!(
+                    "Character literal {} holds more than one character; use a string for text",
+                    &buf.inp[..len]
+                ),
+            };
+            buf.trim(len as i64)?;
+            lexemes.push_back(Lexeme::Literal(Literal::Char(ch)), pos);
+            continue;
+        }
+
+        // comments run up to (but not including) the end of the line
+        if config
+            .line_comments
+            .iter()
+            .any(|marker| buf.inp.starts_with(marker.as_str()))
+        {
+            let end = buf.inp.find('\n').unwrap_or(buf.inp.len());
+            buf.trim(end as i64)?;
+            continue;
+        }
+
+        if let Some((opener, closer)) = config
+            .block_comments
+            .iter()
+            .find(|(opener, _)| buf.inp.starts_with(opener.as_str()))
+        {
+            buf.trim(block_comment_len(&buf.inp, opener, closer)? as i64)?;
+            continue;
+        }
+
+        if let Some((lexeme, len)) = Lexeme::symbol(&buf.inp) {
+            lexemes.push_back(lexeme, pos);
+            buf.trim(len as i64)?;
+            continue;
         }
 
         buf.next();
         buf.trim(buf.idx)?;
     }
 
+    inp[scanned..].chars().for_each(|ch| pos.advance(ch));
+    lexemes.end = pos;
     Ok(lexemes)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lex_str(inp: &str, config: &LexConfig) -> Result<Vec<Lexeme>> {
+        lex(inp.to_string(), config).map(Vec::from)
+    }
+
+    #[test]
+    fn scans_every_number_form() {
+        let config = LexConfig::default();
+        let at = |_| PosInfo { line: 1, column: 1 };
+        for (inp, literal) in [
+            ("0x1F", Literal::Integer(31)),
+            ("1_000", Literal::Integer(1000)),
+            ("2.5", Literal::Float(2.5)),
+            ("1e3", Literal::Float(1000.0)),
+        ] {
+            assert_eq!(scan_number(inp, &config, at).unwrap(), (literal, inp.len()));
+        }
+    }
+
+    #[test]
+    fn hex_literals_option_reads_bare_digits_as_hex() {
+        let at = |_| PosInfo { line: 1, column: 1 };
+        let decimal = LexConfig::default();
+        assert_eq!(
+            scan_number("0x10", &decimal, at).unwrap(),
+            (Literal::Integer(16), 4)
+        );
+        assert_eq!(
+            scan_number("10", &decimal, at).unwrap(),
+            (Literal::Integer(10), 2)
+        );
+        let hex = LexConfig {
+            hex_literals: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            scan_number("10", &hex, at).unwrap(),
+            (Literal::Integer(16), 2)
+        );
+    }
+
+    #[test]
+    fn leading_zeros_are_rejected_when_asked() {
+        let strict = LexConfig {
+            reject_leading_zeros: true,
+            ..Default::default()
+        };
+        let err = lex_str("var x = 007;", &strict).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Decimal literal '007' at line 1, column 9 has leading zeros; write it as `7`, or `0o7` if you meant octal"
+        );
+        assert!(lex_str("var x = 007;", &LexConfig::default()).is_ok());
+    }
+
+    #[test]
+    fn nested_parens_pair_inside_out() {
+        let pos = |line, column| PosInfo { line, column };
+        let pairs = match_delimiters("f((a)\n)", &LexConfig::default()).unwrap();
+        assert_eq!(pairs, [(pos(1, 3), pos(1, 5)), (pos(1, 2), pos(2, 1))]);
+    }
+
+    #[test]
+    fn prefixed_literals_report_the_bad_digit() {
+        let config = LexConfig::default();
+        assert_eq!(
+            lex_str("0xFF", &config).unwrap(),
+            [Lexeme::Literal(Literal::Integer(255))]
+        );
+        let err = lex_str("var x = 0b12;", &config).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid digit '2' in base 2 literal '0b12' at line 1, column 12"
+        );
+    }
+
+    #[test]
+    fn newline_escapes_decode() {
+        let config = LexConfig::default();
+        assert_eq!(
+            lex_str(r#""hello\nworld" '\n'"#, &config).unwrap(),
+            [
+                Lexeme::Literal(Literal::Str("hello\nworld".to_string())),
+                Lexeme::Literal(Literal::Char('\n'))
+            ]
+        );
+    }
+
+    #[test]
+    fn character_literals_hold_one_character() {
+        let config = LexConfig::default();
+        assert_eq!(
+            lex_str("'ab'", &config).unwrap_err().to_string(),
+            "Character literal 'ab' holds more than one character; use a string for text"
+        );
+        assert_eq!(
+            lex_str("''", &config).unwrap_err().to_string(),
+            "Empty character literal ''"
+        );
+    }
+
+    #[test]
+    fn nested_block_comments_keep_line_numbers() {
+        let config = LexConfig::default();
+        assert_eq!(
+            lex_str("/* outer\n/* inner */\nstill outer */ {}", &config).unwrap(),
+            [Lexeme::OpenBrace, Lexeme::CloseBrace]
+        );
+        let err = lex_str("x\n  /* outer /* inner */", &config).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unterminated block comment opened at line 2, column 3"
+        );
+    }
+
+    #[test]
+    fn missing_closing_brace_points_at_the_opener() {
+        let err = lex_str("fn int main() {\n    return 0;\n", &LexConfig::default()).unwrap_err();
+        assert_eq!(err.to_string(), "Unclosed `{` opened at line 1, column 15");
+        let err = lex_str("fn int main() { return 0; } }", &LexConfig::default()).unwrap_err();
+        assert_eq!(err.to_string(), "Unmatched `}` at line 1, column 29");
+    }
+
+    #[test]
+    fn positions_compare_by_value() {
+        let brace = position_of("fn f()\n{", 7);
+        let copy = brace;
+        assert_eq!(brace, copy);
+        assert_eq!(brace, PosInfo { line: 2, column: 1 });
+        assert_ne!(brace, PosInfo { line: 1, column: 8 });
+    }
+
+    #[test]
+    fn raw_strings_keep_their_contents_verbatim() {
+        let config = LexConfig::default();
+        assert_eq!(
+            lex_str(r##"r"C:\path\no\escapes" r#"has "quotes""#"##, &config).unwrap(),
+            [
+                Lexeme::Literal(Literal::Str(r"C:\path\no\escapes".to_string())),
+                Lexeme::Literal(Literal::Str(r#"has "quotes""#.to_string()))
+            ]
+        );
+        let err = lex_str("x = r#\"never closed\"", &config).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unterminated raw string literal opened at line 1, column 5"
+        );
+    }
+
+    #[test]
+    fn oversized_literals_need_wide_literals() {
+        let big = "9223372036854775808";
+        let err = lex_str(big, &LexConfig::default()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Integer literal '9223372036854775808' at line 1, column 1 is too large for an int, which holds at most 9223372036854775807"
+        );
+        let wide = LexConfig {
+            wide_literals: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            lex_str(big, &wide).unwrap(),
+            [Lexeme::Literal(Literal::WideInteger(1 << 63))]
+        );
+    }
+
+    #[test]
+    fn number_can_end_the_file() {
+        let lexemes = lex_str("return 5", &LexConfig::default()).unwrap();
+        assert_eq!(
+            lexemes,
+            [
+                Lexeme::Keyword(Keyword::Return),
+                Lexeme::Literal(Literal::Integer(5))
+            ]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! The compiler's pipeline, for tools that want to drive it without going
+//! through the command line.
+
+pub mod ast;
+pub mod error;
+pub mod lex;
+
+use anyhow::{anyhow, Error};
+use ast::{ir, Root, SymbolTable};
+use lex::Lexemes;
+
+/// How far through the pipeline to run before stopping. Names are resolved
+/// and checked while parsing, so there's no separate phase for them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Phase {
+    /// Stop after lexing, printing the lexemes
+    Lex,
+    /// Stop after parsing, printing the AST
+    Parse,
+    /// Run everything and write the IR
+    Ir,
+}
+
+/// What the pipeline produced by the phase it stopped at.
+#[derive(Debug)]
+pub enum Artifact {
+    Lexemes(Lexemes),
+    Ast(Root),
+    Ir(String),
+}
+
+/// Lexes and parses `src`, throwing the AST away, and returns every syntax
+/// error found rather than stopping at the first.
+pub fn check_syntax(src: &str, config: &lex::LexConfig) -> Result<(), Vec<Error>> {
+    let lexemes = lex::lex(src.to_string(), config).map_err(|err| vec![err])?;
+    ast::parse_all(lexemes, &mut SymbolTable::default()).map(|_| ())
+}
+
+/// An executable needs a `main`, so a file with nothing in it gets its own
+/// error rather than compiling to an empty module. Checking or stopping
+/// early is fine on an empty file.
+pub fn check_executable(lexemes: &Lexemes) -> Result<(), Error> {
+    if lexemes.is_empty() {
+        return Err(anyhow!(
+            "The source file is empty; there's no `main` function to compile"
+        ));
+    }
+    Ok(())
+}
+
+/// Runs the pipeline on `src` up to and including `phase`, returning what
+/// that phase produced, or every error found on the way.
+pub fn compile_until(
+    src: &str,
+    config: &lex::LexConfig,
+    phase: Phase,
+) -> Result<Artifact, Vec<Error>> {
+    let lexemes = lex::lex(src.to_string(), config).map_err(|err| vec![err])?;
+    if phase == Phase::Lex {
+        return Ok(Artifact::Lexemes(lexemes));
+    }
+
+    if phase == Phase::Ir {
+        check_executable(&lexemes).map_err(|err| vec![err])?;
+    }
+    let mut symtab = SymbolTable::default();
+    let ast = ast::parse_all(lexemes, &mut symtab)?;
+    if phase == Phase::Parse {
+        return Ok(Artifact::Ast(ast));
+    }
+
+    let mut ow = ir::OutputWrapper::in_memory();
+    ir::gen_ir(&mut ow, &mut symtab, ast);
+    Ok(Artifact::Ir(ow.into_ir()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lex::Lexeme;
+
+    #[test]
+    fn check_syntax_reports_every_broken_function() {
+        let src = "fn int a() { return 1 }\nfn int b() { return 2; }\nfn int c() { var = 3; return 3; }\n";
+        let errors = check_syntax(src, &lex::LexConfig::default()).unwrap_err();
+        assert_eq!(errors.len(), 2, "{errors:?}");
+    }
+
+    #[test]
+    fn check_syntax_accepts_a_valid_file() {
+        let src = "fn int main() { var x = 1; return x; }";
+        assert!(check_syntax(src, &lex::LexConfig::default()).is_ok());
+    }
+
+    #[test]
+    fn compile_until_stops_at_the_phase_asked_for() {
+        let src = "fn int main() { return 1 + 2; }";
+        let config = lex::LexConfig::default();
+        match compile_until(src, &config, Phase::Lex).unwrap() {
+            Artifact::Lexemes(lexemes) => {
+                assert!(lexemes.iter().any(|lexeme| *lexeme == Lexeme::Addition))
+            }
+            other => panic!("expected lexemes, got {other:?}"),
+        }
+        match compile_until(src, &config, Phase::Parse).unwrap() {
+            Artifact::Ast(root) => assert_eq!(root.stmts.len(), 1),
+            other => panic!("expected an AST, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn compile_until_ir_returns_the_ir() {
+        let src = "fn int main() { return 1 + 2; }";
+        match compile_until(src, &lex::LexConfig::default(), Phase::Ir).unwrap() {
+            Artifact::Ir(ir) => assert!(ir.contains("define i64 @main()"), "{ir}"),
+            other => panic!("expected IR, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn empty_file_is_only_an_error_for_executables() {
+        let config = lex::LexConfig::default();
+        let errors = compile_until("  // nothing\n", &config, Phase::Ir).unwrap_err();
+        assert_eq!(
+            errors[0].to_string(),
+            "The source file is empty; there's no `main` function to compile"
+        );
+        assert!(compile_until("", &config, Phase::Parse).is_ok());
+        assert!(check_syntax("", &config).is_ok());
+    }
+
+    #[test]
+    fn bare_expression_statement_is_an_error() {
+        let src = "fn int main() { 5; return 0; }";
+        let errors = check_syntax(src, &lex::LexConfig::default()).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].to_string(),
+            "Expected a statement, found integer literal `5`"
+        );
+    }
+
+    #[test]
+    fn unknown_statement_is_an_error() {
+        let src = "fn int main() { foo bar; return 0; }";
+        let errors = check_syntax(src, &lex::LexConfig::default()).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].to_string(), "Expected a statement, found `foo`");
+    }
+
+    #[test]
+    fn lowering_twice_gives_the_same_names() {
+        let src = "fn int main() {
+    mut var x = 1 + 2 * 3;
+    while x < 10 { x = x + 1; }
+    if x == 10 { return x; } else { return 0; }
+}";
+        let config = lex::LexConfig::default();
+        let lower = || match compile_until(src, &config, Phase::Ir).unwrap() {
+            Artifact::Ir(ir) => ir,
+            other => panic!("expected IR, got {other:?}"),
+        };
+        let first = lower();
+        assert!(first.contains("%t0") && first.contains(".L0"), "{first}");
+        assert_eq!(first, lower());
+    }
+
+    #[test]
+    fn variables_do_not_outlive_their_block() {
+        let src = "fn int main() { if true { var x = 1; } return x; }";
+        let errors = check_syntax(src, &lex::LexConfig::default()).unwrap_err();
+        assert_eq!(errors.len(), 1);
+    }
+}
@@ -2,41 +2,111 @@ use clap::Parser;
 use std::fs;
 use std::process;
 
-mod ast;
-mod lex;
-
-use ast::ir;
-use ast::SymbolTable;
+use shadow::ast::{self, callgraph, ir, SymbolTable};
+use shadow::{error::ShadowError, lex, Phase};
 
 #[derive(Parser)]
 struct Args {
     filepath: String,
     #[arg(default_value = "a.ll")]
     ofile: String,
+    /// Read integer literals as hexadecimal
+    #[arg(long)]
+    hex: bool,
+    /// Reject decimal integer literals with leading zeros, eg. `007`
+    #[arg(long)]
+    no_leading_zeros: bool,
+    /// Read integer literals too large for an int as i128s, rather than rejecting them
+    #[arg(long)]
+    wide_literals: bool,
+    /// Start line comments with this marker rather than `//`; can be given more than once
+    #[arg(long = "line-comment", value_name = "MARKER")]
+    line_comments: Vec<String>,
+    /// Wrap block comments in these markers rather than `/*` and `*/`; can be given more than once
+    #[arg(long = "block-comment", num_args = 2, value_names = ["OPEN", "CLOSE"])]
+    block_comments: Vec<String>,
+    /// Only check the file parses; don't generate any IR
+    #[arg(long)]
+    check: bool,
+    /// Also write which functions call which to this file, in Graphviz's DOT format
+    #[arg(long)]
+    call_graph: Option<String>,
+    /// The last phase of the pipeline to run
+    #[arg(long, value_enum, default_value_t = Phase::Ir)]
+    until: Phase,
+}
+
+fn report_parse_errors(errs: Vec<anyhow::Error>, src: &str) -> ! {
+    match errs.len() {
+        1 => eprintln!("An error occured whilst parsing the file:"),
+        count => eprintln!("{count} errors occured whilst parsing the file:"),
+    }
+    for err in errs {
+        match err.downcast_ref::<ShadowError>() {
+            Some(err) => eprintln!("{}", err.verbose(src)),
+            None => eprintln!("{}", err),
+        }
+    }
+    process::exit(1);
 }
 
 fn main() {
     let args = Args::parse();
-    let mut ow = ir::OutputWrapper::new(args.ofile).unwrap();
-    let mut symtab = SymbolTable::new();
+    let mut symtab = SymbolTable::default();
 
     let contents = fs::read_to_string(args.filepath).unwrap();
-    let lexemes = lex::lex(contents).unwrap_or_else(|err| {
+    let mut config = lex::LexConfig {
+        hex_literals: args.hex,
+        reject_leading_zeros: args.no_leading_zeros,
+        wide_literals: args.wide_literals,
+        ..Default::default()
+    };
+    if !args.line_comments.is_empty() {
+        config.line_comments = args.line_comments;
+    }
+    if !args.block_comments.is_empty() {
+        config.block_comments = args
+            .block_comments
+            .chunks(2)
+            .map(|pair| (pair[0].clone(), pair[1].clone()))
+            .collect();
+    }
+    let lexemes = lex::lex(contents.clone(), &config).unwrap_or_else(|err| {
         eprintln!("An error occured whilst lexing the file:\n{}", err);
         process::exit(1);
     });
+    if !args.check && args.until == Phase::Ir {
+        if let Err(err) = shadow::check_executable(&lexemes) {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
+
+    if args.check {
+        if let Err(errs) = ast::parse_all(lexemes, &mut symtab) {
+            report_parse_errors(errs, &contents);
+        }
+        println!("No syntax errors found.");
+        return;
+    }
 
     println!("[DBG] Lexemes recieved:\n{:#?}", lexemes);
-    let ast = ast::parse(lexemes, &mut symtab).unwrap();
-    // .unwrap_or_else(|err| {
-    //    eprintln!(
-    //        "An error occured whilst building the AST the file:\n{}",
-    //        err
-    //    );
-    //    process::exit(1);
-    // });
+    if args.until == Phase::Lex {
+        return;
+    }
+
+    let ast = ast::parse_all(lexemes, &mut symtab)
+        .unwrap_or_else(|errs| report_parse_errors(errs, &contents));
     println!("[DBG] AST built, and recieved:\n{:#?}", ast);
+    if let Some(path) = &args.call_graph {
+        fs::write(path, callgraph::call_graph(&ast).to_dot()).unwrap();
+    }
+    if args.until == Phase::Parse {
+        return;
+    }
+
     println!("Generating IR..");
+    let mut ow = ir::OutputWrapper::new(args.ofile).unwrap();
     ir::gen_ir(&mut ow, &mut symtab, ast);
     ow.flush();
 }
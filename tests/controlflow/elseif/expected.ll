@@ -0,0 +1,63 @@
+define i64 @sign(i64 %n) {
+    %n.addr = alloca i64
+    store i64 %n, ptr %n.addr
+    %t0 = load i64, ptr %n.addr
+    %t1 = icmp slt i64 %t0, 0
+    br i1 %t1, label %.L0, label %.L1
+.L0:
+    %t2 = sub i64 0, 1
+    ret i64 %t2
+.L1:
+    %t3 = load i64, ptr %n.addr
+    %t4 = icmp eq i64 %t3, 0
+    br i1 %t4, label %.L2, label %.L3
+.L2:
+    ret i64 0
+.L3:
+    ret i64 1
+    
+}
+    
+define i64 @main() {
+    %grade = alloca i64
+    store i64 0, ptr %grade
+    
+    %score = alloca i64
+    store i64 72, ptr %score
+    
+    %t5 = load i64, ptr %score
+    %t6 = icmp sge i64 %t5, 90
+    br i1 %t6, label %.L5, label %.L6
+.L5:
+    store i64 1, ptr %grade
+    
+    br label %.L11
+.L6:
+    %t7 = load i64, ptr %score
+    %t8 = icmp sge i64 %t7, 70
+    br i1 %t8, label %.L7, label %.L8
+.L7:
+    store i64 2, ptr %grade
+    
+    br label %.L11
+.L8:
+    %t9 = load i64, ptr %score
+    %t10 = icmp sge i64 %t9, 50
+    br i1 %t10, label %.L9, label %.L10
+.L9:
+    store i64 3, ptr %grade
+    
+    br label %.L11
+.L10:
+    store i64 4, ptr %grade
+    
+    br label %.L11
+.L11:
+    
+    %t11 = load i64, ptr %grade
+    %t12 = load i64, ptr %score
+    %t13 = call i64 @sign(i64 %t12)
+    %t14 = add i64 %t11, %t13
+    ret i64 %t14
+}
+    
@@ -0,0 +1,17 @@
+define i64 @main() {
+    %x = alloca i64
+    store i64 3, ptr %x
+    
+    br label %.L0
+.L0:
+    %t0 = load i64, ptr %x
+    %t1 = icmp sgt i64 %t0, 5
+    br i1 %t1, label %.L1, label %.L2
+.L1:
+    br label %.L0
+.L2:
+    
+    %t2 = load i64, ptr %x
+    ret i64 %t2
+}
+    
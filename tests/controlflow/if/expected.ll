@@ -1,14 +1,14 @@
 define i64 @main() {
     %x = alloca i64
-    store i64 10, ptr %x
-    %xderef = load i64, ptr %x
+    store i64 5, ptr %x
+    
+    %t0 = load i64, ptr %x
+    %t1 = icmp sgt i64 %t0, 10
+    br i1 %t1, label %.L0, label %.L1
+.L0:
+    ret i64 0
+.L1:
+    ret i64 1
     
-    %res = icmp sgt i64 %xderef, 50
-    br i1 %res, label %truecase, label %falsecase
-
-falsecase:
-    ret i64 1 
-truecase:
-    ret i64 0 
 }
     
@@ -0,0 +1,27 @@
+define i64 @main() {
+    %x = alloca i64
+    store i64 7, ptr %x
+    
+    %t0 = load i64, ptr %x
+    %t1 = srem i64 %t0, 2
+    %t2 = icmp eq i64 %t1, 0
+    br i1 %t2, label %.L0, label %.L1
+.L0:
+    %t3 = load i64, ptr %x
+    %t4 = sub i64 %t3, 4
+    store i64 %t4, ptr %x
+    
+    br label %.L2
+.L1:
+    %t5 = load i64, ptr %x
+    %t6 = mul i64 3, %t5
+    %t7 = add i64 %t6, 1
+    store i64 %t7, ptr %x
+    
+    br label %.L2
+.L2:
+    
+    %t8 = load i64, ptr %x
+    ret i64 %t8
+}
+    
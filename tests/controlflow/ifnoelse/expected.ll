@@ -0,0 +1,19 @@
+define i64 @main() {
+    %x = alloca i64
+    store i64 1, ptr %x
+    
+    %t0 = load i64, ptr %x
+    %t1 = icmp slt i64 %t0, 5
+    br i1 %t1, label %.L0, label %.L1
+.L0:
+    %t2 = load i64, ptr %x
+    %t3 = add i64 %t2, 10
+    store i64 %t3, ptr %x
+    
+    br label %.L1
+.L1:
+    
+    %t4 = load i64, ptr %x
+    ret i64 %t4
+}
+    
@@ -0,0 +1,11 @@
+define void @main() {
+    br label %.L0
+.L0:
+    br i1 true, label %.L1, label %.L2
+.L1:
+    br label %.L0
+.L2:
+    
+    ret void
+}
+    
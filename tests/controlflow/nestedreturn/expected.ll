@@ -0,0 +1,23 @@
+define i64 @main() {
+    %x = alloca i64
+    store i64 3, ptr %x
+    
+    %t0 = load i64, ptr %x
+    %t1 = icmp slt i64 %t0, 5
+    br i1 %t1, label %.L0, label %.L1
+.L0:
+    %t2 = load i64, ptr %x
+    %t3 = icmp eq i64 %t2, 3
+    br i1 %t3, label %.L2, label %.L3
+.L2:
+    %t4 = load i64, ptr %x
+    %t5 = mul i64 %t4, 2
+    ret i64 %t5
+.L3:
+    
+    ret i64 1
+.L1:
+    
+    ret i64 0
+}
+    
@@ -0,0 +1,37 @@
+define i64 @main() {
+    %i = alloca i64
+    store i64 0, ptr %i
+    
+    %total = alloca i64
+    store i64 0, ptr %total
+    
+    br label %.L0
+.L0:
+    %t0 = load i64, ptr %i
+    %t1 = icmp slt i64 %t0, 10
+    br i1 %t1, label %.L1, label %.L2
+.L1:
+    %t2 = load i64, ptr %i
+    %t3 = add i64 %t2, 1
+    store i64 %t3, ptr %i
+    
+    %t4 = load i64, ptr %i
+    %t5 = srem i64 %t4, 2
+    %t6 = icmp eq i64 %t5, 0
+    br i1 %t6, label %.L3, label %.L4
+.L3:
+    %t7 = load i64, ptr %total
+    %t8 = load i64, ptr %i
+    %t9 = add i64 %t7, %t8
+    store i64 %t9, ptr %total
+    
+    br label %.L4
+.L4:
+    
+    br label %.L0
+.L2:
+    
+    %t10 = load i64, ptr %total
+    ret i64 %t10
+}
+    
@@ -1,3 +1,8 @@
-define void @main(i64 %a, i64 %b) {
-    ret void
+define void @takesargs(i64 %a, i64 %b) {
+    %a.addr = alloca i64
+    store i64 %a, ptr %a.addr
+    %b.addr = alloca i64
+    store i64 %b, ptr %b.addr
+    ret void 
 }
+    
@@ -0,0 +1,14 @@
+define void @helper() {
+    ret void 
+}
+    
+define i64 @square(i64 %x) {
+    %x.addr = alloca i64
+    store i64 %x, ptr %x.addr
+    ret i64 4
+}
+    
+define i64 @main() {
+    ret i64 0
+}
+    
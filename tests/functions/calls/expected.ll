@@ -0,0 +1,47 @@
+define i64 @seven() {
+    ret i64 7
+}
+    
+define i64 @add(i64 %a, i64 %b) {
+    %a.addr = alloca i64
+    store i64 %a, ptr %a.addr
+    %b.addr = alloca i64
+    store i64 %b, ptr %b.addr
+    %t0 = load i64, ptr %a.addr
+    %t1 = load i64, ptr %b.addr
+    %t2 = add i64 %t0, %t1
+    ret i64 %t2
+}
+    
+define i64 @twice(i64 %x) {
+    %x.addr = alloca i64
+    store i64 %x, ptr %x.addr
+    %t3 = load i64, ptr %x.addr
+    %t4 = load i64, ptr %x.addr
+    %t5 = call i64 @add(i64 %t3, i64 %t4)
+    ret i64 %t5
+}
+    
+define void @nothing() {
+    ret void 
+}
+    
+define i64 @main() {
+    call void @nothing()
+    
+    %a = alloca i64
+    %t6 = call i64 @seven()
+    store i64 %t6, ptr %a
+    
+    %b = alloca i64
+    %t7 = call i64 @twice(i64 1)
+    store i64 %t7, ptr %b
+    
+    %t8 = load i64, ptr %a
+    %t9 = load i64, ptr %b
+    %t10 = mul i64 2, 3
+    %t11 = add i64 %t9, %t10
+    %t12 = call i64 @add(i64 %t8, i64 %t11)
+    ret i64 %t12
+}
+    
@@ -0,0 +1,11 @@
+define void @f() {
+    
+    ret void 
+}
+    
+define i64 @main() {
+    
+    
+    ret i64 0
+}
+    
@@ -0,0 +1,10 @@
+define void @nothing() {
+    ret void
+}
+    
+define i64 @main() {
+    call void @nothing()
+    
+    ret i64 0
+}
+    
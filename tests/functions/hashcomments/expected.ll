@@ -0,0 +1,13 @@
+define i64 @double(i64 %x) {
+    %x.addr = alloca i64
+    store i64 %x, ptr %x.addr
+    %t0 = load i64, ptr %x.addr
+    %t1 = mul i64 %t0, 2
+    ret i64 %t1
+}
+    
+define i64 @main() {
+    %t2 = call i64 @double(i64 21)
+    ret i64 %t2
+}
+    
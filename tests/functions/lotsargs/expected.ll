@@ -1,3 +1,56 @@
-define void @main(i64 %a, i64 %b, i64 %c, i64 %d, i64 %e, i64 %f, i64 %g, i64 %h, i64 %i, i64 %j, i64 %k, i64 %l, i64 %m, i64 %n, i64 %o, i64 %p, i64 %q, i64 %r, i64 %s, i64 %t, i64 %u, i64 %v, i64 %w, i64 %x, i64 %y, i64 %z) {
-    ret void
+define void @takesargs(i64 %a, i64 %b, i64 %c, i64 %d, i64 %e, i64 %f, i64 %g, i64 %h, i64 %i, i64 %j, i64 %k, i64 %l, i64 %m, i64 %n, i64 %o, i64 %p, i64 %q, i64 %r, i64 %s, i64 %t, i64 %u, i64 %v, i64 %w, i64 %x, i64 %y, i64 %z) {
+    %a.addr = alloca i64
+    store i64 %a, ptr %a.addr
+    %b.addr = alloca i64
+    store i64 %b, ptr %b.addr
+    %c.addr = alloca i64
+    store i64 %c, ptr %c.addr
+    %d.addr = alloca i64
+    store i64 %d, ptr %d.addr
+    %e.addr = alloca i64
+    store i64 %e, ptr %e.addr
+    %f.addr = alloca i64
+    store i64 %f, ptr %f.addr
+    %g.addr = alloca i64
+    store i64 %g, ptr %g.addr
+    %h.addr = alloca i64
+    store i64 %h, ptr %h.addr
+    %i.addr = alloca i64
+    store i64 %i, ptr %i.addr
+    %j.addr = alloca i64
+    store i64 %j, ptr %j.addr
+    %k.addr = alloca i64
+    store i64 %k, ptr %k.addr
+    %l.addr = alloca i64
+    store i64 %l, ptr %l.addr
+    %m.addr = alloca i64
+    store i64 %m, ptr %m.addr
+    %n.addr = alloca i64
+    store i64 %n, ptr %n.addr
+    %o.addr = alloca i64
+    store i64 %o, ptr %o.addr
+    %p.addr = alloca i64
+    store i64 %p, ptr %p.addr
+    %q.addr = alloca i64
+    store i64 %q, ptr %q.addr
+    %r.addr = alloca i64
+    store i64 %r, ptr %r.addr
+    %s.addr = alloca i64
+    store i64 %s, ptr %s.addr
+    %t.addr = alloca i64
+    store i64 %t, ptr %t.addr
+    %u.addr = alloca i64
+    store i64 %u, ptr %u.addr
+    %v.addr = alloca i64
+    store i64 %v, ptr %v.addr
+    %w.addr = alloca i64
+    store i64 %w, ptr %w.addr
+    %x.addr = alloca i64
+    store i64 %x, ptr %x.addr
+    %y.addr = alloca i64
+    store i64 %y, ptr %y.addr
+    %z.addr = alloca i64
+    store i64 %z, ptr %z.addr
+    ret void 
 }
+    
@@ -1,9 +1,20 @@
 define void @first() {
     ret void 
 }
+    
 define i64 @second() {
     ret i64 3
 }
+    
 define i64 @third(i64 %a, i64 %b, i64 %c, i64 %d) {
+    %a.addr = alloca i64
+    store i64 %a, ptr %a.addr
+    %b.addr = alloca i64
+    store i64 %b, ptr %b.addr
+    %c.addr = alloca i64
+    store i64 %c, ptr %c.addr
+    %d.addr = alloca i64
+    store i64 %d, ptr %d.addr
     ret i64 0
 }
+    
@@ -0,0 +1,15 @@
+define i64 @parens() {
+    ret i64 7
+}
+    
+define i64 @bare() {
+    ret i64 7
+}
+    
+define i64 @main() {
+    %t0 = call i64 @parens()
+    %t1 = call i64 @bare()
+    %t2 = sub i64 %t0, %t1
+    ret i64 %t2
+}
+    
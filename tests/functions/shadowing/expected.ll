@@ -0,0 +1,22 @@
+define i64 @double(i64 %x) {
+    %x.addr = alloca i64
+    store i64 %x, ptr %x.addr
+    %x.1 = alloca i64
+    %t0 = load i64, ptr %x.addr
+    %t1 = mul i64 %t0, 2
+    store i64 %t1, ptr %x.1
+    
+    %t2 = load i64, ptr %x.1
+    ret i64 %t2
+}
+    
+define i64 @main() {
+    %x = alloca i64
+    store i64 1, ptr %x
+    
+    %x.1 = alloca i1
+    store i1 true, ptr %x.1
+    
+    ret i64 0
+}
+    
@@ -0,0 +1,4 @@
+define i64 @main() {
+    ret i64 0
+}
+    
@@ -1,9 +1,9 @@
 define i64 @main() {
     %x = alloca i64
-    %addtemp = add i64 2, 3
-    store i64 %addtemp, ptr %x
+    %t0 = add i64 2, 3
+    store i64 %t0, ptr %x
     
-    %xderef = load i64, ptr %x
-    ret i64 %xderef
+    %t1 = load i64, ptr %x
+    ret i64 %t1
 }
     
@@ -0,0 +1,30 @@
+define i1 @compare() {
+    %a = alloca i1
+    %t0 = icmp sle i64 1, 2
+    store i1 %t0, ptr %a
+    
+    %b = alloca i1
+    %t1 = add i64 1, 2
+    %t2 = icmp eq i64 3, %t1
+    store i1 %t2, ptr %b
+    
+    %c = alloca i1
+    %t3 = fcmp une double 0x4004000000000000, 0x3FF0000000000000
+    store i1 %t3, ptr %c
+    
+    %d = alloca i1
+    %t4 = icmp sge i64 4, 5
+    store i1 %t4, ptr %d
+    
+    %e = alloca i1
+    %t5 = load i1, ptr %a
+    %t6 = load i1, ptr %b
+    %t7 = icmp eq i1 %t5, %t6
+    store i1 %t7, ptr %e
+    
+    %t8 = load i1, ptr %a
+    %t9 = load i1, ptr %b
+    %t10 = icmp eq i1 %t8, %t9
+    ret i1 %t10
+}
+    
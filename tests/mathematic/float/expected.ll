@@ -0,0 +1,13 @@
+define double @scale() {
+    %half = alloca double
+    store double 0x3FE0000000000000, ptr %half
+    
+    %x = alloca double
+    %t0 = load double, ptr %half
+    %t1 = fmul double 0x406F400000000000, %t0
+    store double %t1, ptr %x
+    
+    %t2 = load double, ptr %x
+    ret double %t2
+}
+    
@@ -0,0 +1,40 @@
+define i64 @main() {
+    %zero = alloca double
+    store double 0x0000000000000000, ptr %zero
+    
+    %nan = alloca double
+    %t0 = load double, ptr %zero
+    %t1 = load double, ptr %zero
+    %t2 = fdiv double %t0, %t1
+    store double %t2, ptr %nan
+    
+    %unequal = alloca i1
+    %t3 = load double, ptr %nan
+    %t4 = load double, ptr %nan
+    %t5 = fcmp une double %t3, %t4
+    store i1 %t5, ptr %unequal
+    
+    %equal = alloca i1
+    %t6 = load double, ptr %nan
+    %t7 = load double, ptr %nan
+    %t8 = fcmp oeq double %t6, %t7
+    store i1 %t8, ptr %equal
+    
+    %less = alloca i1
+    %t9 = load double, ptr %nan
+    %t10 = fcmp olt double %t9, 0x3FF0000000000000
+    store i1 %t10, ptr %less
+    
+    %t11 = load i1, ptr %unequal
+    %t12 = zext i1 %t11 to i64
+    %t13 = mul i64 %t12, 4
+    %t14 = load i1, ptr %equal
+    %t15 = zext i1 %t14 to i64
+    %t16 = mul i64 %t15, 2
+    %t17 = add i64 %t13, %t16
+    %t18 = load i1, ptr %less
+    %t19 = zext i1 %t18 to i64
+    %t20 = add i64 %t17, %t19
+    ret i64 %t20
+}
+    
@@ -0,0 +1,13 @@
+define i64 @main() {
+    %x = alloca i64
+    %t0 = add i64 255, 10
+    store i64 %t0, ptr %x
+    
+    %y = alloca i64
+    %t1 = sub i64 1000, 15
+    store i64 %t1, ptr %y
+    
+    %t2 = load i64, ptr %y
+    ret i64 %t2
+}
+    
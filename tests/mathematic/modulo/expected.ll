@@ -0,0 +1,37 @@
+define i64 @main() {
+    %x = alloca i64
+    %t0 = srem i64 7, 3
+    store i64 %t0, ptr %x
+    
+    %a = alloca i64
+    %t1 = sub i64 0, 7
+    %t2 = srem i64 %t1, 3
+    store i64 %t2, ptr %a
+    
+    %b = alloca i64
+    %t3 = sub i64 0, 3
+    %t4 = srem i64 7, %t3
+    store i64 %t4, ptr %b
+    
+    %c = alloca i64
+    %t5 = sub i64 0, 7
+    %t6 = sub i64 0, 3
+    %t7 = srem i64 %t5, %t6
+    store i64 %t7, ptr %c
+    
+    %t8 = load i64, ptr %x
+    %t9 = mul i64 %t8, 1000
+    %t10 = load i64, ptr %a
+    %t11 = add i64 %t10, 1
+    %t12 = mul i64 %t11, 100
+    %t13 = add i64 %t9, %t12
+    %t14 = load i64, ptr %b
+    %t15 = add i64 %t14, 1
+    %t16 = mul i64 %t15, 10
+    %t17 = add i64 %t13, %t16
+    %t18 = load i64, ptr %c
+    %t19 = add i64 %t18, 1
+    %t20 = add i64 %t17, %t19
+    ret i64 %t20
+}
+    
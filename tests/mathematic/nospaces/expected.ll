@@ -0,0 +1,14 @@
+define i64 @main() {
+    %x = alloca i64
+    %t0 = add i64 1, 2
+    store i64 %t0, ptr %x
+    
+    %y = alloca i64
+    %t1 = load i64, ptr %x
+    %t2 = mul i64 %t1, 3
+    store i64 %t2, ptr %y
+    
+    %t3 = load i64, ptr %y
+    ret i64 %t3
+}
+    
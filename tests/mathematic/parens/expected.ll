@@ -0,0 +1,22 @@
+define i64 @main() {
+    %a = alloca i64
+    %t0 = add i64 1, 2
+    %t1 = mul i64 %t0, 3
+    store i64 %t1, ptr %a
+    
+    %b = alloca i64
+    %t2 = mul i64 2, 3
+    %t3 = add i64 1, %t2
+    store i64 %t3, ptr %b
+    
+    %c = alloca i64
+    %t4 = load i64, ptr %a
+    %t5 = load i64, ptr %b
+    %t6 = sub i64 %t4, %t5
+    %t7 = mul i64 %t6, 2
+    store i64 %t7, ptr %c
+    
+    %t8 = load i64, ptr %c
+    ret i64 %t8
+}
+    
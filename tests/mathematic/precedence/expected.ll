@@ -0,0 +1,34 @@
+define i64 @main() {
+    %a = alloca i64
+    %t0 = mul i64 2, 3
+    %t1 = add i64 1, %t0
+    store i64 %t1, ptr %a
+    
+    %b = alloca i64
+    %t2 = sub i64 10, 3
+    %t3 = sub i64 %t2, 2
+    store i64 %t3, ptr %b
+    
+    %c = alloca i64
+    %t4 = load i64, ptr %a
+    %t5 = load i64, ptr %b
+    %t6 = mul i64 %t4, %t5
+    %t7 = srem i64 %t6, 4
+    %t8 = sub i64 %t7, 1
+    store i64 %t8, ptr %c
+    
+    %big = alloca i1
+    %t9 = load i64, ptr %a
+    %t10 = add i64 %t9, 1
+    %t11 = load i64, ptr %b
+    %t12 = mul i64 %t11, 2
+    %t13 = icmp sgt i64 %t10, %t12
+    store i1 %t13, ptr %big
+    
+    %t14 = load i1, ptr %big
+    %t15 = zext i1 %t14 to i64
+    %t16 = load i64, ptr %c
+    %t17 = add i64 %t15, %t16
+    ret i64 %t17
+}
+    
@@ -0,0 +1,12 @@
+define i64 @main() {
+    %a = alloca i64
+    %t0 = add i64 0, 0
+    store i64 %t0, ptr %a
+    
+    %b = alloca i64
+    %t1 = mul i64 0, 0
+    store i64 %t1, ptr %b
+    
+    ret i64 0
+}
+    
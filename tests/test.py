@@ -44,7 +44,12 @@ def block(name):
         test = os.path.join(absroot, prog)
         result = os.path.join(absroot, "result.ll")
         expected = findfile(absroot, files, "expected.ll", ".ll")
-        run = subprocess.run(["cargo",  "run", test, result], cwd="../", capture_output=True) 
+        # an optional `flags` file holds extra arguments for the compiler
+        flags = []
+        if "flags" in files:
+            with open(os.path.join(absroot, "flags")) as f:
+                flags = f.read().split()
+        run = subprocess.run(["cargo",  "run", "--", *flags, test, result], cwd="../", capture_output=True) 
         # https://doc.rust-lang.org/cargo/commands/cargo-run.html#exit-status
         if run.returncode == 101:
             error(f"the compiler did not return succesfully; output:\n\n{run.stderr.decode('utf-8')}")
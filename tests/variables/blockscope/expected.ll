@@ -0,0 +1,77 @@
+define i64 @main() {
+    %x = alloca i64
+    store i64 1, ptr %x
+    
+    %t0 = load i64, ptr %x
+    %t1 = icmp sgt i64 %t0, 0
+    br i1 %t1, label %.L0, label %.L1
+.L0:
+    %x.1 = alloca i64
+    store i64 2, ptr %x.1
+    
+    br label %.L1
+.L1:
+    
+    %total = alloca i64
+    %t2 = load i64, ptr %x
+    store i64 %t2, ptr %total
+    
+    br i1 true, label %.L2, label %.L3
+.L2:
+    %y = alloca i64
+    store i64 10, ptr %y
+    
+    %t3 = load i64, ptr %total
+    %t4 = load i64, ptr %y
+    %t5 = add i64 %t3, %t4
+    store i64 %t5, ptr %total
+    
+    br label %.L3
+.L3:
+    
+    br i1 true, label %.L4, label %.L5
+.L4:
+    %y.1 = alloca i64
+    store i64 20, ptr %y.1
+    
+    %t6 = load i64, ptr %total
+    %t7 = load i64, ptr %y.1
+    %t8 = add i64 %t6, %t7
+    store i64 %t8, ptr %total
+    
+    br label %.L5
+.L5:
+    
+    %i = alloca i64
+    store i64 0, ptr %i
+    
+    br label %.L6
+.L6:
+    %t9 = load i64, ptr %i
+    %t10 = icmp slt i64 %t9, 3
+    br i1 %t10, label %.L7, label %.L8
+.L7:
+    %y.2 = alloca i64
+    store i64 100, ptr %y.2
+    
+    %t11 = load i64, ptr %total
+    %t12 = load i64, ptr %y.2
+    %t13 = add i64 %t11, %t12
+    store i64 %t13, ptr %total
+    
+    %t14 = load i64, ptr %i
+    %t15 = add i64 %t14, 1
+    store i64 %t15, ptr %i
+    
+    br label %.L6
+.L8:
+    
+    %y.3 = alloca i64
+    store i64 1000, ptr %y.3
+    
+    %t16 = load i64, ptr %total
+    %t17 = load i64, ptr %y.3
+    %t18 = add i64 %t16, %t17
+    ret i64 %t18
+}
+    
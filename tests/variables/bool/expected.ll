@@ -0,0 +1,16 @@
+define i64 @main() {
+    %yes = alloca i1
+    store i1 true, ptr %yes
+    
+    %trueish = alloca i1
+    store i1 false, ptr %trueish
+    
+    %n = alloca i64
+    %t0 = load i1, ptr %yes
+    %t1 = zext i1 %t0 to i64
+    store i64 %t1, ptr %n
+    
+    %t2 = load i64, ptr %n
+    ret i64 %t2
+}
+    
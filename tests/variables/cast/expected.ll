@@ -0,0 +1,14 @@
+define i64 @main() {
+    %big = alloca i1
+    %t0 = icmp sgt i64 5, 3
+    store i1 %t0, ptr %big
+    
+    %x = alloca i64
+    %t1 = load i1, ptr %big
+    %t2 = zext i1 %t1 to i64
+    store i64 %t2, ptr %x
+    
+    %t3 = load i64, ptr %x
+    ret i64 %t3
+}
+    
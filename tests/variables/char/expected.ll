@@ -0,0 +1,11 @@
+define i32 @letter() {
+    %c = alloca i32
+    store i32 97, ptr %c
+    
+    %brace = alloca i32
+    store i32 123, ptr %brace
+    
+    %t0 = load i32, ptr %c
+    ret i32 %t0
+}
+    
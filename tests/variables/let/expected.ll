@@ -0,0 +1,21 @@
+define i64 @main() {
+    %x = alloca i64
+    store i64 5, ptr %x
+    
+    %big = alloca i1
+    %t0 = load i64, ptr %x
+    %t1 = icmp sgt i64 %t0, 3
+    store i1 %t1, ptr %big
+    
+    %half = alloca double
+    store double 0x3FE0000000000000, ptr %half
+    
+    %y = alloca i64
+    %t2 = load i64, ptr %x
+    %t3 = mul i64 %t2, 2
+    store i64 %t3, ptr %y
+    
+    %t4 = load i64, ptr %y
+    ret i64 %t4
+}
+    
@@ -0,0 +1,20 @@
+define i64 @main() {
+    %x = alloca i64
+    store i64 1, ptr %x
+    
+    %t0 = load i64, ptr %x
+    %t1 = add i64 %t0, 10
+    store i64 %t1, ptr %x
+    
+    %y = alloca i64
+    store i64 2, ptr %y
+    
+    %t2 = load i64, ptr %y
+    %t3 = load i64, ptr %x
+    %t4 = mul i64 %t2, %t3
+    store i64 %t4, ptr %y
+    
+    %t5 = load i64, ptr %y
+    ret i64 %t5
+}
+    
@@ -0,0 +1,81 @@
+define i8 @high_nibble(i8 %byte) {
+    %byte.addr = alloca i8
+    store i8 %byte, ptr %byte.addr
+    %t0 = load i8, ptr %byte.addr
+    %t1 = trunc i64 16 to i8
+    %t2 = udiv i8 %t0, %t1
+    ret i8 %t2
+}
+    
+define i64 @main() {
+    %small = alloca i8
+    %t3 = trunc i64 100 to i8
+    store i8 %t3, ptr %small
+    
+    %wider = alloca i16
+    %t4 = load i8, ptr %small
+    %t5 = sext i8 %t4 to i16
+    %t6 = trunc i64 3 to i16
+    %t7 = mul i16 %t5, %t6
+    store i16 %t7, ptr %wider
+    
+    %negative = alloca i32
+    %t8 = sub i64 0, 5
+    %t9 = trunc i64 %t8 to i32
+    store i32 %t9, ptr %negative
+    
+    %word = alloca i64
+    store i64 8, ptr %word
+    
+    %byte = alloca i8
+    %t10 = trunc i64 250 to i8
+    store i8 %t10, ptr %byte
+    
+    %half = alloca i16
+    %t11 = load i8, ptr %byte
+    %t12 = zext i8 %t11 to i16
+    %t13 = trunc i64 6 to i16
+    %t14 = add i16 %t12, %t13
+    store i16 %t14, ptr %half
+    
+    %big = alloca i32
+    %t15 = load i16, ptr %half
+    %t16 = zext i16 %t15 to i32
+    %t17 = trunc i64 7 to i32
+    %t18 = urem i32 %t16, %t17
+    store i32 %t18, ptr %big
+    
+    %huge = alloca i64
+    %t19 = load i32, ptr %big
+    %t20 = zext i32 %t19 to i64
+    store i64 %t20, ptr %huge
+    
+    %nibble = alloca i8
+    %t21 = load i8, ptr %byte
+    %t22 = call i8 @high_nibble(i8 %t21)
+    store i8 %t22, ptr %nibble
+    
+    %below = alloca i1
+    %t23 = load i8, ptr %byte
+    %t24 = trunc i64 200 to i8
+    %t25 = icmp ult i8 %t23, %t24
+    store i1 %t25, ptr %below
+    
+    %t26 = load i16, ptr %wider
+    %t27 = sext i16 %t26 to i64
+    %t28 = load i32, ptr %negative
+    %t29 = sext i32 %t28 to i64
+    %t30 = add i64 %t27, %t29
+    %t31 = load i64, ptr %word
+    %t32 = add i64 %t30, %t31
+    %t33 = load i64, ptr %huge
+    %t34 = add i64 %t32, %t33
+    %t35 = load i8, ptr %nibble
+    %t36 = zext i8 %t35 to i64
+    %t37 = add i64 %t34, %t36
+    %t38 = load i1, ptr %below
+    %t39 = zext i1 %t38 to i64
+    %t40 = add i64 %t37, %t39
+    ret i64 %t40
+}
+    